@@ -0,0 +1,536 @@
+use std::collections::VecDeque;
+
+use crate::logic::MOVES;
+use crate::rules::MAX_HEALTH;
+use crate::{Board, Coord, Direction};
+
+// Big enough for the largest boards the game engine offers (25x25)
+pub const MAX_CELLS: usize = 25 * 25;
+const WORDS: usize = (MAX_CELLS + 63) / 64;
+
+// One bit per cell, numbered from the bottom left corner a row at a time
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Mask([u64; WORDS]);
+
+impl Mask {
+    pub fn contains(&self, cell: u16) -> bool {
+        self.0[cell as usize / 64] & (1 << (cell % 64)) != 0
+    }
+
+    pub fn insert(&mut self, cell: u16) {
+        self.0[cell as usize / 64] |= 1 << (cell % 64);
+    }
+
+    pub fn remove(&mut self, cell: u16) {
+        self.0[cell as usize / 64] &= !(1 << (cell % 64));
+    }
+
+    pub fn union(&self, other: &Mask) -> Mask {
+        let mut union = *self;
+        for (word, other) in union.0.iter_mut().zip(other.0.iter()) {
+            *word |= other;
+        }
+        union
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = u16> + '_ {
+        self.0.iter().enumerate().flat_map(|(index, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros();
+                word &= word - 1;
+                Some((index * 64) as u16 + bit as u16)
+            })
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Snake {
+    // Where the snake sits in the original board, to look up everything else about them
+    index: usize,
+    health: i32,
+    // Head first, like the request
+    body: VecDeque<u16>,
+    cells: Mask,
+}
+
+impl Snake {
+    fn head(&self) -> u16 {
+        self.body[0]
+    }
+
+    fn grow(&mut self) {
+        if let Some(&tail) = self.body.back() {
+            self.body.push_back(tail);
+        }
+    }
+
+    // A tail stacked on the segment before it stays put next turn
+    fn tail_will_move(&self) -> bool {
+        let length = self.body.len();
+        length > 1 && self.body[length - 1] != self.body[length - 2]
+    }
+}
+
+// A copy of the board that is cheap to clone and step forward, for the random playouts
+// in mcts and the alpha-beta searches. The searches turn it back into a Board to score
+// their leaves, since that needs a Grid built from one.
+// Only the standard, wrapped and constrictor rules are played, squads and stacked
+// hazards need the full Board.
+#[derive(Clone, Debug)]
+pub struct Bitboard {
+    width: i32,
+    height: i32,
+    wrapped: bool,
+    constrictor: bool,
+    hazard_damage_per_turn: i32,
+    food: Mask,
+    hazards: Mask,
+    snakes: Vec<Snake>,
+}
+
+impl Bitboard {
+    // Boards too big to fit, using squad rules or stacking hazards can't be converted
+    pub fn from_board(board: &Board) -> Option<Bitboard> {
+        if board.width <= 0 || board.height <= 0 {
+            return None;
+        }
+        if (board.width * board.height) as usize > MAX_CELLS {
+            return None;
+        }
+        let squad = &board.ruleset.settings.squad;
+        if squad.allow_body_collisions
+            || squad.shared_elimination
+            || squad.shared_health
+            || squad.shared_length
+        {
+            return None;
+        }
+
+        let mut bitboard = Bitboard {
+            width: board.width,
+            height: board.height,
+            wrapped: board.ruleset.wrapped(),
+            constrictor: board.ruleset.constrictor(),
+            hazard_damage_per_turn: board.ruleset.settings.hazard_damage_per_turn,
+            food: Mask::default(),
+            hazards: Mask::default(),
+            snakes: Vec::with_capacity(board.snakes.len()),
+        };
+        for spot in board.food.iter() {
+            bitboard.food.insert(bitboard.cell(spot)?);
+        }
+        for spot in board.hazards.iter() {
+            let cell = bitboard.cell(spot)?;
+            if bitboard.hazards.contains(cell) {
+                return None;
+            }
+            bitboard.hazards.insert(cell);
+        }
+        for (index, snake) in board.snakes.iter().enumerate() {
+            let mut body = VecDeque::with_capacity(snake.body.len() + 1);
+            let mut cells = Mask::default();
+            for spot in snake.body.iter() {
+                let cell = bitboard.cell(spot)?;
+                body.push_back(cell);
+                cells.insert(cell);
+            }
+            if body.is_empty() {
+                return None;
+            }
+            bitboard.snakes.push(Snake {
+                index,
+                health: snake.health,
+                body,
+                cells,
+            });
+        }
+        Some(bitboard)
+    }
+
+    // Puts the bitboard back into the board it came from, keeping everything
+    // the bitboard doesn't track (names, ids, shouts...) from the original.
+    pub fn to_board(&self, original: &Board) -> Board {
+        let mut board = original.clone();
+        board.food = self.food.cells().map(|cell| self.coord(cell)).collect();
+        board.hazards = self.hazards.cells().map(|cell| self.coord(cell)).collect();
+        board.snakes = self
+            .snakes
+            .iter()
+            .map(|snake| {
+                let mut restored = original.snakes[snake.index].clone();
+                restored.health = snake.health;
+                restored.body = snake.body.iter().map(|cell| self.coord(*cell)).collect();
+                restored.head = self.coord(snake.head());
+                restored.length = snake.body.len() as i32;
+                restored
+            })
+            .collect();
+        board
+    }
+
+    pub fn snake_count(&self) -> usize {
+        self.snakes.len()
+    }
+
+    // Where each snake still on the board sat in the original board
+    pub fn survivors(&self) -> impl Iterator<Item = usize> + '_ {
+        self.snakes.iter().map(|snake| snake.index)
+    }
+
+    // Cells nobody can move into next turn. Tails that are about to move on don't count.
+    pub fn blocked(&self) -> Mask {
+        let mut blocked = Mask::default();
+        for snake in self.snakes.iter() {
+            let mut cells = snake.cells;
+            if !self.constrictor && snake.tail_will_move() {
+                if let Some(tail) = snake.body.back() {
+                    cells.remove(*tail);
+                }
+            }
+            blocked = blocked.union(&cells);
+        }
+        blocked
+    }
+
+    // The moves a snake can make without hitting a wall or a body
+    pub fn legal_moves(&self, snake: usize) -> Vec<Direction> {
+        let blocked = self.blocked();
+        let head = self.snakes[snake].head();
+        MOVES
+            .iter()
+            .copied()
+            .filter(|chosen| match self.step(head, *chosen) {
+                Some(cell) => !blocked.contains(cell),
+                None => false,
+            })
+            .collect()
+    }
+
+    // Plays one turn the same way rules::advance does, with one move for each snake in order
+    pub fn advance(&mut self, moves: &[Direction]) {
+        let mut eliminated = vec![false; self.snakes.len()];
+
+        for (index, chosen) in moves.iter().enumerate().take(self.snakes.len()) {
+            let next = self.step(self.snakes[index].head(), *chosen);
+            let snake = &mut self.snakes[index];
+            let head = match next {
+                Some(head) => head,
+                None => {
+                    eliminated[index] = true;
+                    continue;
+                }
+            };
+            if let Some(tail) = snake.body.pop_back() {
+                if snake.body.back() != Some(&tail) {
+                    snake.cells.remove(tail);
+                }
+            }
+            snake.body.push_front(head);
+            snake.cells.insert(head);
+        }
+
+        for snake in self.snakes.iter_mut() {
+            snake.health -= 1;
+            let head = snake.head();
+            if self.hazards.contains(head) && !self.food.contains(head) {
+                snake.health -= self.hazard_damage_per_turn;
+            }
+        }
+
+        if self.constrictor {
+            for snake in self.snakes.iter_mut() {
+                snake.health = MAX_HEALTH;
+                snake.grow();
+            }
+            self.food = Mask::default();
+        } else {
+            let mut eaten = Mask::default();
+            for snake in self.snakes.iter_mut() {
+                if self.food.contains(snake.head()) {
+                    snake.health = MAX_HEALTH;
+                    snake.grow();
+                    eaten.insert(snake.head());
+                }
+            }
+            for cell in eaten.cells() {
+                self.food.remove(cell);
+            }
+        }
+
+        for (index, snake) in self.snakes.iter().enumerate() {
+            if snake.health <= 0 {
+                eliminated[index] = true;
+            }
+        }
+        let mut collided = eliminated.clone();
+        for (index, snake) in self.snakes.iter().enumerate() {
+            if eliminated[index] {
+                continue;
+            }
+            let head = snake.head();
+            for (other_index, other) in self.snakes.iter().enumerate() {
+                if eliminated[other_index] || !other.cells.contains(head) {
+                    continue;
+                }
+                let hit_body =
+                    head != other.head() || other.body.iter().skip(1).any(|cell| *cell == head);
+                let lost_head_to_head = other_index != index
+                    && head == other.head()
+                    && other.body.len() >= snake.body.len();
+                if hit_body || lost_head_to_head {
+                    collided[index] = true;
+                    break;
+                }
+            }
+        }
+
+        let mut index = 0;
+        self.snakes.retain(|_| {
+            index += 1;
+            !collided[index - 1]
+        });
+    }
+
+    fn cell(&self, spot: &Coord) -> Option<u16> {
+        if spot.x < 0 || spot.y < 0 || spot.x >= self.width || spot.y >= self.height {
+            return None;
+        }
+        Some((spot.y * self.width + spot.x) as u16)
+    }
+
+    fn coord(&self, cell: u16) -> Coord {
+        Coord {
+            x: cell as i32 % self.width,
+            y: cell as i32 / self.width,
+        }
+    }
+
+    fn step(&self, cell: u16, chosen: Direction) -> Option<u16> {
+        let Coord { x, y } = self.coord(cell);
+        let (mut x, mut y) = match chosen {
+            Direction::Up => (x, y + 1),
+            Direction::Down => (x, y - 1),
+            Direction::Left => (x - 1, y),
+            Direction::Right => (x + 1, y),
+        };
+        if self.wrapped {
+            x = x.rem_euclid(self.width);
+            y = y.rem_euclid(self.height);
+        }
+        self.cell(&Coord { x, y })
+    }
+}
+
+#[cfg(test)]
+mod bitboard_tests {
+    use super::*;
+    use crate::logic::Weights;
+    use crate::rules;
+    use crate::GameState;
+    use std::collections::{HashMap, HashSet};
+
+    // https://docs.battlesnake.com/references/api/sample-move-request
+    const SAMPLE_MOVE: &str = r#"{
+        "game": {
+            "id": "game-00fe20da-94ad-11ea-bb37-0242ac130002",
+            "ruleset": { "name": "standard", "version": "v1.2.3" },
+            "timeout": 500
+        },
+        "turn": 14,
+        "board": {
+            "height": 11,
+            "width": 11,
+            "food": [{ "x": 5, "y": 5 }, { "x": 9, "y": 0 }, { "x": 2, "y": 6 }],
+            "hazards": [{ "x": 3, "y": 2 }],
+            "snakes": [
+                {
+                    "id": "snake-508e96ac-94ad-11ea-bb37-0242ac130002",
+                    "name": "My Snake",
+                    "health": 54,
+                    "body": [{ "x": 0, "y": 0 }, { "x": 1, "y": 0 }, { "x": 2, "y": 0 }],
+                    "latency": "111",
+                    "head": { "x": 0, "y": 0 },
+                    "length": 3,
+                    "shout": "why are we shouting??",
+                    "squad": ""
+                },
+                {
+                    "id": "snake-b67f4906-94ae-11ea-bb37-0242ac130002",
+                    "name": "Another Snake",
+                    "health": 16,
+                    "body": [{ "x": 5, "y": 4 }, { "x": 5, "y": 3 }, { "x": 6, "y": 3 }, { "x": 6, "y": 2 }],
+                    "latency": "222",
+                    "head": { "x": 5, "y": 4 },
+                    "length": 4,
+                    "shout": "I'm not really sure...",
+                    "squad": ""
+                }
+            ]
+        },
+        "you": {
+            "id": "snake-508e96ac-94ad-11ea-bb37-0242ac130002",
+            "name": "My Snake",
+            "health": 54,
+            "body": [{ "x": 0, "y": 0 }, { "x": 1, "y": 0 }, { "x": 2, "y": 0 }],
+            "latency": "111",
+            "head": { "x": 0, "y": 0 },
+            "length": 3,
+            "shout": "why are we shouting??",
+            "squad": ""
+        }
+    }"#;
+
+    // The first turn of a wrapped game, when every snake is stacked on one spot
+    const SAMPLE_WRAPPED_START: &str = r#"{
+        "game": {
+            "id": "game-wrapped",
+            "ruleset": { "name": "wrapped", "version": "v1.2.3" },
+            "timeout": 500
+        },
+        "turn": 0,
+        "board": {
+            "height": 11,
+            "width": 11,
+            "food": [{ "x": 0, "y": 10 }, { "x": 10, "y": 0 }],
+            "hazards": [],
+            "snakes": [
+                {
+                    "id": "cornelius",
+                    "name": "CorneliusCodes",
+                    "health": 100,
+                    "body": [{ "x": 0, "y": 0 }, { "x": 0, "y": 0 }, { "x": 0, "y": 0 }],
+                    "latency": "0",
+                    "head": { "x": 0, "y": 0 },
+                    "length": 3,
+                    "shout": "",
+                    "squad": ""
+                },
+                {
+                    "id": "hettie",
+                    "name": "Hettie",
+                    "health": 100,
+                    "body": [{ "x": 10, "y": 10 }, { "x": 10, "y": 10 }, { "x": 10, "y": 10 }],
+                    "latency": "0",
+                    "head": { "x": 10, "y": 10 },
+                    "length": 3,
+                    "shout": "",
+                    "squad": ""
+                }
+            ]
+        },
+        "you": {
+            "id": "cornelius",
+            "name": "CorneliusCodes",
+            "health": 100,
+            "body": [{ "x": 0, "y": 0 }, { "x": 0, "y": 0 }, { "x": 0, "y": 0 }],
+            "latency": "0",
+            "head": { "x": 0, "y": 0 },
+            "length": 3,
+            "shout": "",
+            "squad": ""
+        }
+    }"#;
+
+    fn sample(request: &str) -> Board {
+        let state: GameState = serde_json::from_str(request).unwrap();
+        state.prepare(Weights::default()).board
+    }
+
+    // Food and hazards come back in board order rather than request order
+    fn assert_same_board(board: &Board, expected: &Board) {
+        let food: HashSet<Coord> = board.food.iter().copied().collect();
+        let expected_food: HashSet<Coord> = expected.food.iter().copied().collect();
+        assert_eq!(food, expected_food);
+        let hazards: HashSet<Coord> = board.hazards.iter().copied().collect();
+        let expected_hazards: HashSet<Coord> = expected.hazards.iter().copied().collect();
+        assert_eq!(hazards, expected_hazards);
+        assert_eq!(
+            serde_json::to_value(&board.snakes).unwrap(),
+            serde_json::to_value(&expected.snakes).unwrap()
+        );
+        assert_eq!(board.width, expected.width);
+        assert_eq!(board.height, expected.height);
+    }
+
+    #[test]
+    fn round_trips_the_sample_requests() {
+        for request in [SAMPLE_MOVE, SAMPLE_WRAPPED_START].iter() {
+            let board = sample(request);
+            let bitboard = Bitboard::from_board(&board).unwrap();
+            assert_same_board(&bitboard.to_board(&board), &board);
+        }
+    }
+
+    #[test]
+    fn legal_moves_avoid_walls_and_bodies() {
+        let bitboard = Bitboard::from_board(&sample(SAMPLE_MOVE)).unwrap();
+        assert_eq!(bitboard.legal_moves(0), vec![Direction::Up]);
+        assert_eq!(
+            bitboard.legal_moves(1),
+            vec![Direction::Up, Direction::Left, Direction::Right]
+        );
+    }
+
+    #[test]
+    fn wrapped_moves_come_back_on_the_other_side() {
+        let bitboard = Bitboard::from_board(&sample(SAMPLE_WRAPPED_START)).unwrap();
+        assert_eq!(bitboard.legal_moves(0), MOVES.to_vec());
+    }
+
+    #[test]
+    fn advances_like_the_rules() {
+        let cases: Vec<(&str, Vec<Direction>)> = vec![
+            (SAMPLE_MOVE, vec![Direction::Up, Direction::Up]),
+            (SAMPLE_MOVE, vec![Direction::Right, Direction::Down]),
+            (SAMPLE_MOVE, vec![Direction::Down, Direction::Left]),
+            (SAMPLE_WRAPPED_START, vec![Direction::Down, Direction::Up]),
+            (
+                SAMPLE_WRAPPED_START,
+                vec![Direction::Left, Direction::Right],
+            ),
+        ];
+        for (request, chosen) in cases {
+            let board = sample(request);
+            let moves: HashMap<String, Direction> = board
+                .snakes
+                .iter()
+                .zip(chosen.iter())
+                .map(|(snake, chosen)| (snake.id.clone(), *chosen))
+                .collect();
+            let expected = rules::advance(&board, &moves);
+
+            let mut bitboard = Bitboard::from_board(&board).unwrap();
+            bitboard.advance(&chosen);
+            assert_same_board(&bitboard.to_board(&board), &expected);
+        }
+    }
+
+    #[test]
+    fn squads_need_the_full_board() {
+        let mut board = sample(SAMPLE_MOVE);
+        board.ruleset.settings.squad.shared_health = true;
+        assert!(Bitboard::from_board(&board).is_none());
+    }
+
+    #[test]
+    fn stacked_hazards_need_the_full_board() {
+        let mut board = sample(SAMPLE_MOVE);
+        board.hazards.push(board.hazards[0]);
+        assert!(Bitboard::from_board(&board).is_none());
+    }
+
+    #[test]
+    fn mask_lists_its_cells() {
+        let mut mask = Mask::default();
+        mask.insert(3);
+        mask.insert(64);
+        mask.insert(624);
+        mask.remove(3);
+        assert_eq!(mask.cells().collect::<Vec<u16>>(), vec![64, 624]);
+    }
+}
@@ -0,0 +1,271 @@
+use std::time::Instant;
+
+use crate::grid::Grid;
+use crate::logic::{self, MOVES, PECKISH};
+use crate::search::{self, Evaluator, LOSS, WIN};
+use crate::{Battlesnake, Board, Direction};
+
+// With only two snakes left the whole game tree is small enough to go a lot deeper
+const MAX_DEPTH: u32 = 32;
+// Both snakes going out together only beats losing, so Cornelius never forces one
+// while any other way still leaves them in the game
+const DRAW: i32 = LOSS / 2;
+// Each cell more than the rival controls is worth this much
+const TERRITORY_WEIGHT: i32 = 10;
+// Each point of health Cornelius will still have when they eat
+const HEALTH_WEIGHT: i32 = 5;
+// Being longer wins head-to-heads, but only the first few segments of a lead make a difference
+const LENGTH_WEIGHT: i32 = 25;
+const MAX_LEAD: i32 = 3;
+
+// One rival left, who isn't on Cornelius' squad
+pub fn is_duel(board: &Board, me: &Battlesnake) -> bool {
+    board.snakes.len() == 2
+        && board.snakes.iter().any(|snake| snake.id == me.id)
+        && board
+            .snakes
+            .iter()
+            .any(|snake| snake.id != me.id && !snake.is_teammate(me))
+}
+
+// Plays out a duel with the same alpha-beta as crowded boards, only deeper
+pub fn best_move(board: &Board, me: &Battlesnake, deadline: Instant) -> Direction {
+    search::best_move(&Duel, board, me, deadline)
+}
+
+// Scores a duel by how it ends, or by who has the upper hand when the search stops
+struct Duel;
+
+impl Evaluator for Duel {
+    fn max_depth(&self) -> u32 {
+        MAX_DEPTH
+    }
+
+    fn game_over(&self, survivors: &[&str], my_id: &str, depth: u32) -> Option<i32> {
+        let me = survivors.contains(&my_id);
+        let rival = survivors.iter().any(|id| *id != my_id);
+        match (me, rival) {
+            (true, true) => None,
+            (true, false) => Some(WIN + depth as i32),
+            (false, true) => Some(LOSS - depth as i32),
+            (false, false) => Some(DRAW),
+        }
+    }
+
+    fn value(&self, board: &Board, me: &Battlesnake) -> i32 {
+        board
+            .snakes
+            .iter()
+            .find(|snake| snake.id != me.id)
+            .map_or(WIN, |rival| value_of_duel(board, me, rival))
+    }
+}
+
+// Board control decides a duel, going by the best cell Cornelius can move to next,
+// with a little extra for being long enough to win head-to-heads and for keeping fed
+fn value_of_duel(board: &Board, me: &Battlesnake, rival: &Battlesnake) -> i32 {
+    let grid = Grid::for_snake(board, me);
+    let territory = MOVES
+        .iter()
+        .filter_map(|chosen| logic::territory(&board.step(&me.head, *chosen), board, &grid, me))
+        .map(|(mine, theirs)| mine - theirs)
+        .max()
+        .unwrap_or(-board.width * board.height);
+    let lead = (me.length - rival.length).clamp(-MAX_LEAD, MAX_LEAD);
+    TERRITORY_WEIGHT * territory
+        + LENGTH_WEIGHT * lead
+        + HEALTH_WEIGHT * value_of_health(board, &grid, me)
+}
+
+// Food doesn't matter until Cornelius is peckish. After that it's the health they'll
+// have left when they get to eat, with nothing for food they can't get to first.
+fn value_of_health(board: &Board, grid: &Grid, me: &Battlesnake) -> i32 {
+    if me.health > PECKISH {
+        return PECKISH;
+    }
+    logic::best_meal(board, grid, me).map_or(0, |meal| meal.health.min(PECKISH))
+}
+
+#[cfg(test)]
+mod is_duel_tests {
+    use super::*;
+    use crate::Coord;
+
+    fn snake(id: &str, x: i32) -> Battlesnake {
+        Battlesnake {
+            id: id.to_string(),
+            head: Coord { x, y: 5 },
+            body: vec![Coord { x, y: 5 }, Coord { x, y: 4 }, Coord { x, y: 3 }],
+            length: 3,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn one_rival_left() {
+        let me = snake("me", 2);
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), snake("hettie", 8)],
+            ..Default::default()
+        };
+        assert!(is_duel(&board, &me));
+    }
+
+    #[test]
+    fn not_while_more_are_alive() {
+        let me = snake("me", 2);
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), snake("hettie", 5), snake("maud", 8)],
+            ..Default::default()
+        };
+        assert!(!is_duel(&board, &me));
+    }
+
+    #[test]
+    fn not_against_a_teammate() {
+        let me = Battlesnake {
+            squad: Some("corneliuses".to_string()),
+            ..snake("me", 2)
+        };
+        let hettie = Battlesnake {
+            squad: Some("corneliuses".to_string()),
+            ..snake("hettie", 8)
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), hettie],
+            ..Default::default()
+        };
+        assert!(!is_duel(&board, &me));
+    }
+}
+
+#[cfg(test)]
+mod duel_search_tests {
+    use super::*;
+    use crate::Coord;
+
+    // Hettie is stuck in the corner with only one way out, right next to Cornelius
+    fn cornered(my_length: i32) -> (Board, Battlesnake) {
+        let body: Vec<Coord> = (1..=my_length).map(|y| Coord { x: 1, y }).collect();
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 1, y: 1 },
+            body,
+            length: my_length,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 0, y: 0 },
+            body: vec![
+                Coord { x: 0, y: 0 },
+                Coord { x: 1, y: 0 },
+                Coord { x: 2, y: 0 },
+                Coord { x: 3, y: 0 },
+            ],
+            length: 4,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), hettie],
+            ..Default::default()
+        };
+        (board, me)
+    }
+
+    #[test]
+    fn takes_the_head_to_head_when_longer() {
+        let (board, me) = cornered(5);
+        assert_eq!(
+            search::search(&Duel, &board, &me, 2, None),
+            Some(Direction::Left)
+        );
+    }
+
+    #[test]
+    fn does_not_settle_for_a_draw_when_ahead_on_territory() {
+        // Both the same length, so the head-to-head would take them both out
+        let (board, me) = cornered(4);
+        assert!(value_of_duel(&board, &me, &board.snakes[1]) > DRAW);
+        assert_ne!(
+            search::search(&Duel, &board, &me, 2, None),
+            Some(Direction::Left)
+        );
+    }
+    #[test]
+    fn does_not_risk_a_draw_when_behind_on_territory() {
+        // Side by side up the wall, where Hettie can meet Cornelius head on if they turn in
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 6, y: 5 },
+            body: (2..=5).rev().map(|y| Coord { x: 6, y }).collect(),
+            length: 4,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            head: Coord { x: 5, y: 4 },
+            body: (1..=4).rev().map(|y| Coord { x: 5, y }).collect(),
+            length: 4,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 7,
+            width: 7,
+            snakes: vec![me.clone(), hettie],
+            ..Default::default()
+        };
+        assert!(value_of_duel(&board, &me, &board.snakes[1]) < 0);
+        assert_eq!(
+            search::search(&Duel, &board, &me, 2, None),
+            Some(Direction::Up)
+        );
+    }
+
+    #[test]
+    fn hungry_snakes_want_food_close_by() {
+        let head = Coord { x: 5, y: 5 };
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head,
+            body: vec![head, head.down(), head.down().down()],
+            health: 30,
+            length: 3,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            head: Coord { x: 9, y: 1 },
+            body: vec![
+                Coord { x: 9, y: 1 },
+                Coord { x: 9, y: 0 },
+                Coord { x: 10, y: 0 },
+            ],
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 5, y: 8 }],
+            snakes: vec![me.clone(), hettie],
+            ..Default::default()
+        };
+        // The same food, only further away
+        let further = Board {
+            food: vec![Coord { x: 1, y: 1 }],
+            ..board.clone()
+        };
+        let rival = &board.snakes[1];
+        assert!(value_of_duel(&board, &me, rival) > value_of_duel(&further, &me, rival));
+    }
+}
@@ -0,0 +1,90 @@
+// Boards shared by the tests of the different brains, so they all face the same positions
+use crate::{Battlesnake, Board, Coord};
+
+// Food sits at the end of a dead end between Hettie and Maud. It looks tempting
+// without looking ahead, but going in means never getting out again.
+pub fn dead_end() -> (Board, Battlesnake) {
+    let me = Battlesnake {
+        id: "me".to_string(),
+        head: Coord { x: 3, y: 5 },
+        body: vec![
+            Coord { x: 3, y: 5 },
+            Coord { x: 4, y: 5 },
+            Coord { x: 5, y: 5 },
+        ],
+        health: 50,
+        length: 3,
+        ..Default::default()
+    };
+    let hettie = Battlesnake {
+        id: "hettie".to_string(),
+        name: "Hettie".to_string(),
+        head: Coord { x: 6, y: 8 },
+        body: vec![
+            Coord { x: 6, y: 8 },
+            Coord { x: 5, y: 8 },
+            Coord { x: 4, y: 8 },
+            Coord { x: 3, y: 8 },
+            Coord { x: 2, y: 8 },
+            Coord { x: 2, y: 7 },
+            Coord { x: 2, y: 6 },
+            Coord { x: 1, y: 6 },
+            Coord { x: 0, y: 6 },
+            Coord { x: 0, y: 7 },
+            Coord { x: 1, y: 7 },
+            Coord { x: 1, y: 8 },
+            Coord { x: 0, y: 8 },
+            Coord { x: 0, y: 9 },
+        ],
+        length: 14,
+        ..Default::default()
+    };
+    let maud = Battlesnake {
+        id: "maud".to_string(),
+        name: "Maud".to_string(),
+        head: Coord { x: 6, y: 2 },
+        body: vec![
+            Coord { x: 6, y: 2 },
+            Coord { x: 5, y: 2 },
+            Coord { x: 4, y: 2 },
+            Coord { x: 3, y: 2 },
+            Coord { x: 2, y: 2 },
+            Coord { x: 2, y: 3 },
+            Coord { x: 2, y: 4 },
+            Coord { x: 1, y: 4 },
+            Coord { x: 0, y: 4 },
+            Coord { x: 0, y: 3 },
+            Coord { x: 1, y: 3 },
+            Coord { x: 1, y: 2 },
+            Coord { x: 0, y: 2 },
+            Coord { x: 0, y: 1 },
+        ],
+        length: 14,
+        ..Default::default()
+    };
+    let board = Board {
+        height: 11,
+        width: 11,
+        food: vec![Coord { x: 2, y: 5 }],
+        snakes: vec![me.clone(), hettie, maud],
+        ..Default::default()
+    };
+    (board, me)
+}
+
+// Cornelius is in the top right corner heading right, so the only way out is down
+pub fn facing_the_wall() -> (Board, Battlesnake) {
+    let head = Coord { x: 9, y: 9 };
+    let me = Battlesnake {
+        body: vec![head, head.left(), head.left().left()],
+        head,
+        ..Default::default()
+    };
+    let board = Board {
+        height: 10,
+        width: 10,
+        snakes: vec![me.clone()],
+        ..Default::default()
+    };
+    (board, me)
+}
@@ -0,0 +1,657 @@
+use std::collections::VecDeque;
+
+use crate::{Battlesnake, Board, Coord};
+
+const FOOD: u8 = 1;
+const SNAKE: u8 = 1 << 1;
+const THREAT: u8 = 1 << 2;
+// Opponents any further away than this aren't worth checking for traps
+const TRAP_REACH: i32 = 4;
+
+// What is in every cell of the board, worked out once per board so that
+// looking up a spot doesn't mean searching through every snake.
+// Anything off the edge of the board is a wall.
+pub struct Grid {
+    width: i32,
+    height: i32,
+    wrapped: bool,
+    cells: Vec<u8>,
+    // Hazards can be stacked on a cell, each one hurting as much again
+    hazards: Vec<u8>,
+    // How many turns until the snake in each cell has moved on
+    free_after: Vec<u16>,
+    // Percentage chance a shorter snake moves into each cell next
+    prey_odds: Vec<u8>,
+    // The first turn an opponent could get to each cell, and the longest one to get there then
+    rivals: Vec<Option<(u16, i32)>>,
+    // Who would get to each cell first if Cornelius stayed where they are
+    claims: Vec<Option<Claim>>,
+    // How many cells each snake claims, by where they sit in the board
+    claimed: Vec<i32>,
+    // How many opponents each move Cornelius can make leaves without room to fit their body
+    traps: Vec<(Coord, i32)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Claim {
+    pub turn: u16,
+    // Where the snake sits in the board, or None when two snakes of the same length
+    // get there together
+    pub owner: Option<usize>,
+    pub length: i32,
+}
+
+impl Grid {
+    pub fn new(board: &Board) -> Grid {
+        Grid::build(board, |_| true)
+    }
+
+    // Also marks the cells where a snake at least as long as Cornelius could move next.
+    // Squads that allow body collisions can slither straight through each other,
+    // so teammates are left off entirely.
+    pub fn for_snake(board: &Board, me: &Battlesnake) -> Grid {
+        let passable = board.ruleset.settings.squad.allow_body_collisions;
+        let mut grid = Grid::build(board, |snake| !(passable && snake.is_teammate(me)));
+        for snake in board.snakes.iter() {
+            if snake.id == me.id || snake.is_teammate(me) || snake.length < me.length {
+                continue;
+            }
+            for neighbour in board.neighbours(&snake.head).iter() {
+                // Constrictor tails never move, so no snake can slip into a body
+                if board.ruleset.constrictor() && grid.has_snake(neighbour) {
+                    continue;
+                }
+                grid.mark(neighbour, THREAT);
+            }
+        }
+
+        // Shorter snakes with fewer ways out are more likely to move where Cornelius can get them
+        for snake in board.snakes.iter() {
+            if snake.id == me.id || snake.is_teammate(me) || snake.length >= me.length {
+                continue;
+            }
+            let escapes: Vec<Coord> = board
+                .neighbours(&snake.head)
+                .iter()
+                .copied()
+                .filter(|neighbour| matches!(grid.free_after(neighbour), Some(free) if free <= 1))
+                .collect();
+            for escape in escapes.iter() {
+                grid.add_prey_odds(escape, 100 / escapes.len() as u8);
+            }
+        }
+
+        grid.race_rivals(board, me);
+        grid.claim_territory(board, me);
+        grid.find_traps(board, me);
+        grid
+    }
+
+    fn build<F: Fn(&Battlesnake) -> bool>(board: &Board, include: F) -> Grid {
+        let size = (board.width.max(0) * board.height.max(0)) as usize;
+        let mut grid = Grid {
+            width: board.width.max(0),
+            height: board.height.max(0),
+            wrapped: board.ruleset.wrapped(),
+            cells: vec![0; size],
+            hazards: vec![0; size],
+            free_after: vec![0; size],
+            prey_odds: vec![0; size],
+            rivals: vec![None; size],
+            claims: vec![None; size],
+            claimed: vec![0; board.snakes.len()],
+            traps: Vec::new(),
+        };
+        for spot in board.food.iter() {
+            grid.mark(spot, FOOD);
+        }
+        for spot in board.hazards.iter() {
+            if let Some(index) = grid.index(spot) {
+                grid.hazards[index] = grid.hazards[index].saturating_add(1);
+            }
+        }
+        for snake in board.snakes.iter().filter(|snake| include(snake)) {
+            // The tail moves on after one turn, the segment before it after two, and so on.
+            // Constrictor tails never move.
+            let length = snake.body.len();
+            let turns_left = |index: usize| {
+                if board.ruleset.constrictor() {
+                    u16::MAX
+                } else {
+                    (length - index) as u16
+                }
+            };
+            grid.mark(&snake.head, SNAKE);
+            if snake.body.first() != Some(&snake.head) {
+                grid.occupy(&snake.head, turns_left(0).saturating_add(1));
+            }
+            for (index, spot) in snake.body.iter().enumerate() {
+                grid.mark(spot, SNAKE);
+                grid.occupy(spot, turns_left(index));
+            }
+        }
+        grid
+    }
+
+    pub fn is_wall(&self, spot: &Coord) -> bool {
+        self.index(spot).is_none()
+    }
+
+    pub fn has_food(&self, spot: &Coord) -> bool {
+        self.has(spot, FOOD)
+    }
+
+    pub fn has_hazard(&self, spot: &Coord) -> bool {
+        self.hazards(spot) > 0
+    }
+
+    pub fn hazards(&self, spot: &Coord) -> i32 {
+        self.index(spot)
+            .map_or(0, |index| self.hazards[index].into())
+    }
+
+    pub fn has_snake(&self, spot: &Coord) -> bool {
+        self.has(spot, SNAKE)
+    }
+
+    pub fn might_have_snake(&self, spot: &Coord) -> bool {
+        self.has(spot, THREAT)
+    }
+
+    pub fn prey_odds(&self, spot: &Coord) -> i32 {
+        self.index(spot)
+            .map_or(0, |index| self.prey_odds[index].into())
+    }
+
+    // When the quickest opponent gets to a spot and how long they are, if they can get there at all
+    pub fn rival_arrival(&self, spot: &Coord) -> Option<(u16, i32)> {
+        self.index(spot).and_then(|index| self.rivals[index])
+    }
+
+    // Who gets to a spot first out of everyone but Cornelius
+    pub fn claim(&self, spot: &Coord) -> Option<Claim> {
+        self.index(spot).and_then(|index| self.claims[index])
+    }
+
+    // How many cells a snake claims, not counting the one their head is on
+    pub fn claimed(&self, owner: usize) -> i32 {
+        self.claimed.get(owner).copied().unwrap_or(0)
+    }
+
+    // How many opponents Cornelius traps by moving to a spot next to their head
+    pub fn trapped(&self, spot: &Coord) -> i32 {
+        self.traps
+            .iter()
+            .find(|(trap, _)| trap == spot)
+            .map_or(0, |(_, trapped)| *trapped)
+    }
+
+    // How many turns until nothing is in the way at a spot, or None for walls
+    pub fn free_after(&self, spot: &Coord) -> Option<u16> {
+        self.index(spot).map(|index| self.free_after[index])
+    }
+
+    fn has(&self, spot: &Coord, flag: u8) -> bool {
+        self.index(spot)
+            .map_or(false, |index| self.cells[index] & flag != 0)
+    }
+
+    fn mark(&mut self, spot: &Coord, flag: u8) {
+        if let Some(index) = self.index(spot) {
+            self.cells[index] |= flag;
+        }
+    }
+
+    // A stacked tail stays as long as the segment furthest from the tail
+    fn occupy(&mut self, spot: &Coord, turns: u16) {
+        if let Some(index) = self.index(spot) {
+            self.free_after[index] = self.free_after[index].max(turns);
+        }
+    }
+
+    // Breadth first from every opponent head at once, waiting for tails to move on
+    fn race_rivals(&mut self, board: &Board, me: &Battlesnake) {
+        let mut queue = VecDeque::new();
+        for snake in board.snakes.iter() {
+            if snake.id == me.id || snake.is_teammate(me) {
+                continue;
+            }
+            if let Some(index) = self.index(&snake.head) {
+                let longest =
+                    self.rivals[index].map_or(snake.length, |(_, length)| length.max(snake.length));
+                self.rivals[index] = Some((0, longest));
+                queue.push_back(snake.head);
+            }
+        }
+
+        while let Some(spot) = queue.pop_front() {
+            let (turn, length) = match self.index(&spot).and_then(|index| self.rivals[index]) {
+                Some(rival) => rival,
+                None => continue,
+            };
+            let next = turn + 1;
+            for neighbour in board.neighbours(&spot).iter() {
+                let index = match self.index(neighbour) {
+                    Some(index) if self.free_after[index] <= next => index,
+                    _ => continue,
+                };
+                match self.rivals[index] {
+                    None => {
+                        self.rivals[index] = Some((next, length));
+                        queue.push_back(*neighbour);
+                    }
+                    Some((turn, longest)) if turn == next && length > longest => {
+                        self.rivals[index] = Some((next, length));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // Splits the board between every snake but Cornelius by who can get to each cell first.
+    // Longer snakes win ties, and snakes of the same length leave the cell to nobody.
+    fn claim_territory(&mut self, board: &Board, me: &Battlesnake) {
+        let mut queue = VecDeque::new();
+        for (owner, snake) in board.snakes.iter().enumerate() {
+            if snake.id == me.id {
+                continue;
+            }
+            if let Some(index) = self.index(&snake.head) {
+                self.claims[index] = Some(Claim {
+                    turn: 0,
+                    owner: Some(owner),
+                    length: snake.length,
+                });
+                queue.push_back((snake.head, 0, owner));
+            }
+        }
+
+        while let Some((spot, turn, owner)) = queue.pop_front() {
+            // Someone else got here at the same time and took it
+            match self.claim(&spot) {
+                Some(claim) if claim.owner == Some(owner) => {}
+                _ => continue,
+            }
+            let next = turn + 1;
+            let length = board.snakes[owner].length;
+            for neighbour in board.neighbours(&spot).iter() {
+                let index = match self.index(neighbour) {
+                    Some(index) if self.free_after[index] <= next => index,
+                    _ => continue,
+                };
+                match &mut self.claims[index] {
+                    None => {
+                        self.claims[index] = Some(Claim {
+                            turn: next,
+                            owner: Some(owner),
+                            length,
+                        });
+                        queue.push_back((*neighbour, next, owner));
+                    }
+                    Some(claim) if claim.turn == next => match claim.owner {
+                        Some(other) if other == owner => {}
+                        Some(_) if length > claim.length => {
+                            claim.owner = Some(owner);
+                            claim.length = length;
+                            queue.push_back((*neighbour, next, owner));
+                        }
+                        Some(_) if length == claim.length => claim.owner = None,
+                        _ => {}
+                    },
+                    Some(_) => {}
+                }
+            }
+        }
+
+        for claim in self.claims.iter().flatten().filter(|claim| claim.turn > 0) {
+            if let Some(owner) = claim.owner {
+                self.claimed[owner] += 1;
+            }
+        }
+    }
+
+    // Works out once per board which of Cornelius' moves leave nearby opponents with less room
+    // than they are long. Each way out only needs filling again for a move that lands in it.
+    fn find_traps(&mut self, board: &Board, me: &Battlesnake) {
+        let spots: Vec<Coord> = board
+            .neighbours(&me.head)
+            .iter()
+            .copied()
+            .filter(|spot| matches!(self.free_after(spot), Some(free) if free <= 1))
+            .collect();
+        let mut traps: Vec<(Coord, i32)> = spots.iter().map(|spot| (*spot, 0)).collect();
+        for snake in board.snakes.iter() {
+            if snake.id == me.id || snake.is_teammate(me) {
+                continue;
+            }
+            let nearby: Vec<usize> = (0..spots.len())
+                .filter(|index| board.distance(&snake.head, &spots[*index]) <= TRAP_REACH)
+                .collect();
+            if nearby.is_empty() {
+                continue;
+            }
+
+            let escapes: Vec<(Coord, Vec<Coord>)> = board
+                .neighbours(&snake.head)
+                .iter()
+                .map(|escape| (*escape, self.room(board, escape, None, snake.length)))
+                .collect();
+            // Snakes that are stuck already don't make any move of Cornelius' a trap
+            if escapes
+                .iter()
+                .all(|(_, room)| (room.len() as i32) < snake.length)
+            {
+                continue;
+            }
+            for index in nearby {
+                let spot = &spots[index];
+                let cornered = escapes.iter().all(|(escape, room)| {
+                    if escape == spot || (room.len() as i32) < snake.length {
+                        true
+                    } else if !room.contains(spot) {
+                        false
+                    } else {
+                        (self.room(board, escape, Some(spot), snake.length).len() as i32)
+                            < snake.length
+                    }
+                });
+                if cornered {
+                    traps[index].1 += 1;
+                }
+            }
+        }
+        self.traps = traps;
+    }
+
+    // The cells a snake could get to from a spot, stopping once there are enough of them
+    // to fit the snake. Anything blocked stays in the way for good.
+    fn room(
+        &self,
+        board: &Board,
+        from: &Coord,
+        blocked: Option<&Coord>,
+        enough: i32,
+    ) -> Vec<Coord> {
+        let open = |spot: &Coord, turn: u16| {
+            Some(spot) != blocked && matches!(self.free_after(spot), Some(free) if free <= turn)
+        };
+        let mut room = Vec::new();
+        let start = board.wrap(*from);
+        if !open(&start, 1) {
+            return room;
+        }
+        let mut queue = VecDeque::new();
+        room.push(start);
+        queue.push_back((start, 1));
+        while let Some((spot, turn)) = queue.pop_front() {
+            if room.len() as i32 >= enough {
+                break;
+            }
+            for neighbour in board.neighbours(&spot).iter() {
+                if !room.contains(neighbour) && open(neighbour, turn + 1) {
+                    room.push(*neighbour);
+                    queue.push_back((*neighbour, turn + 1));
+                }
+            }
+        }
+        room
+    }
+
+    fn add_prey_odds(&mut self, spot: &Coord, odds: u8) {
+        if let Some(index) = self.index(spot) {
+            self.prey_odds[index] = self.prey_odds[index].saturating_add(odds).min(100);
+        }
+    }
+
+    fn index(&self, spot: &Coord) -> Option<usize> {
+        let (mut x, mut y) = (spot.x, spot.y);
+        if self.wrapped && self.width > 0 && self.height > 0 {
+            x = x.rem_euclid(self.width);
+            y = y.rem_euclid(self.height);
+        }
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        Some((y * self.width + x) as usize)
+    }
+}
+
+#[cfg(test)]
+mod grid_tests {
+    use super::*;
+    use crate::{Ruleset, RulesetName};
+
+    #[test]
+    fn marks_everything_on_the_board() {
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 2, y: 3 },
+            body: vec![Coord { x: 2, y: 3 }, Coord { x: 3, y: 3 }],
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 5, y: 5 }],
+            hazards: vec![Coord { x: 0, y: 10 }],
+            snakes: vec![hettie],
+            ..Default::default()
+        };
+        let grid = Grid::new(&board);
+        assert!(grid.has_food(&Coord { x: 5, y: 5 }));
+        assert!(grid.has_hazard(&Coord { x: 0, y: 10 }));
+        assert!(grid.has_snake(&Coord { x: 2, y: 3 }));
+        assert!(grid.has_snake(&Coord { x: 3, y: 3 }));
+        assert!(!grid.has_snake(&Coord { x: 4, y: 3 }));
+    }
+
+    #[test]
+    fn stacks_hazards() {
+        let board = Board {
+            height: 11,
+            width: 11,
+            hazards: vec![
+                Coord { x: 0, y: 10 },
+                Coord { x: 0, y: 10 },
+                Coord { x: 1, y: 10 },
+            ],
+            ..Default::default()
+        };
+        let grid = Grid::new(&board);
+        assert_eq!(grid.hazards(&Coord { x: 0, y: 10 }), 2);
+        assert_eq!(grid.hazards(&Coord { x: 1, y: 10 }), 1);
+        assert_eq!(grid.hazards(&Coord { x: 2, y: 10 }), 0);
+        assert!(!grid.has_hazard(&Coord { x: 2, y: 10 }));
+    }
+
+    #[test]
+    fn tails_free_up_first() {
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 2, y: 3 },
+            body: vec![
+                Coord { x: 2, y: 3 },
+                Coord { x: 3, y: 3 },
+                Coord { x: 4, y: 3 },
+                Coord { x: 4, y: 3 },
+            ],
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie],
+            ..Default::default()
+        };
+        let grid = Grid::new(&board);
+        assert_eq!(grid.free_after(&Coord { x: 2, y: 3 }), Some(4));
+        assert_eq!(grid.free_after(&Coord { x: 3, y: 3 }), Some(3));
+        assert_eq!(grid.free_after(&Coord { x: 4, y: 3 }), Some(2));
+        assert_eq!(grid.free_after(&Coord { x: 5, y: 3 }), Some(0));
+        assert_eq!(grid.free_after(&Coord { x: 11, y: 3 }), None);
+    }
+
+    #[test]
+    fn cornered_prey_is_easier_to_catch() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 5, y: 5 },
+            body: vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }],
+            length: 5,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 0, y: 10 },
+            body: vec![
+                Coord { x: 0, y: 10 },
+                Coord { x: 0, y: 9 },
+                Coord { x: 0, y: 8 },
+            ],
+            length: 3,
+            ..Default::default()
+        };
+        let maud = Battlesnake {
+            id: "maud".to_string(),
+            name: "Maud".to_string(),
+            head: Coord { x: 7, y: 7 },
+            body: vec![
+                Coord { x: 7, y: 7 },
+                Coord { x: 7, y: 6 },
+                Coord { x: 7, y: 5 },
+            ],
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), hettie, maud],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        assert_eq!(grid.prey_odds(&Coord { x: 1, y: 10 }), 100);
+        assert_eq!(grid.prey_odds(&Coord { x: 7, y: 8 }), 33);
+        assert_eq!(grid.prey_odds(&Coord { x: 5, y: 6 }), 0);
+    }
+
+    #[test]
+    fn rivals_race_from_their_heads() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 0, y: 0 },
+            body: vec![Coord { x: 0, y: 0 }],
+            length: 3,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 4, y: 0 },
+            body: vec![Coord { x: 4, y: 0 }],
+            length: 2,
+            ..Default::default()
+        };
+        let maud = Battlesnake {
+            id: "maud".to_string(),
+            name: "Maud".to_string(),
+            head: Coord { x: 8, y: 0 },
+            body: vec![Coord { x: 8, y: 0 }],
+            length: 6,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 1,
+            width: 10,
+            snakes: vec![me.clone(), hettie, maud],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        assert_eq!(grid.rival_arrival(&Coord { x: 2, y: 0 }), Some((2, 2)));
+        assert_eq!(grid.rival_arrival(&Coord { x: 6, y: 0 }), Some((2, 6)));
+        assert_eq!(grid.rival_arrival(&Coord { x: 9, y: 0 }), Some((1, 6)));
+        // Cornelius will have moved on by the time Hettie gets there
+        assert_eq!(grid.rival_arrival(&Coord { x: 0, y: 0 }), Some((4, 2)));
+    }
+
+    #[test]
+    fn splits_the_board_without_cornelius() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 0, y: 0 },
+            body: vec![Coord { x: 0, y: 0 }],
+            length: 3,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 4, y: 0 },
+            body: vec![Coord { x: 4, y: 0 }],
+            length: 2,
+            ..Default::default()
+        };
+        let maud = Battlesnake {
+            id: "maud".to_string(),
+            name: "Maud".to_string(),
+            head: Coord { x: 8, y: 0 },
+            body: vec![Coord { x: 8, y: 0 }],
+            length: 6,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 1,
+            width: 10,
+            snakes: vec![me.clone(), hettie, maud],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        // Both get to the middle together, and Maud is longer
+        assert_eq!(
+            grid.claim(&Coord { x: 6, y: 0 }),
+            Some(Claim {
+                turn: 2,
+                owner: Some(2),
+                length: 6
+            })
+        );
+        assert_eq!(grid.claimed(0), 0);
+        assert_eq!(grid.claimed(1), 5);
+        assert_eq!(grid.claimed(2), 3);
+    }
+
+    #[test]
+    fn walls_surround_the_board() {
+        let board = Board {
+            height: 7,
+            width: 11,
+            ..Default::default()
+        };
+        let grid = Grid::new(&board);
+        assert!(grid.is_wall(&Coord { x: -1, y: 3 }));
+        assert!(grid.is_wall(&Coord { x: 11, y: 3 }));
+        assert!(grid.is_wall(&Coord { x: 3, y: 7 }));
+        assert!(!grid.is_wall(&Coord { x: 10, y: 6 }));
+    }
+
+    #[test]
+    fn wrapped_boards_have_no_walls() {
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 0, y: 5 }],
+            ruleset: Ruleset {
+                name: RulesetName::Wrapped,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let grid = Grid::new(&board);
+        assert!(!grid.is_wall(&Coord { x: 11, y: 5 }));
+        assert!(grid.has_food(&Coord { x: 11, y: 5 }));
+    }
+}
@@ -1,50 +1,128 @@
 use rocket_contrib::json::JsonValue;
-use std::collections::HashMap;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::time::Instant;
 
 use log::info;
 
-use crate::{Battlesnake, Board, Coord, Game};
+use crate::grid::Grid;
+use crate::personality::Appearance;
+use crate::{duel, rules, search, solo};
+use crate::{Battlesnake, Board, Coord, Direction, Game};
 
-pub fn get_info() -> JsonValue {
+pub fn get_info(appearance: &Appearance) -> JsonValue {
     info!("INFO");
 
-    // Personalize the look of your snake per https://docs.battlesnake.com/references/personalization
     return json!({
         "apiversion": "1",
         "author": "ChaelCodes",
-        "color": "#c88b4c",
-        "head": "bendr",
-        "tail": "round-bum",
+        "color": appearance.color,
+        "head": appearance.head,
+        "tail": appearance.tail,
     });
 }
 
 pub fn start(game: &Game, _turn: &u32, _board: &Board, _me: &Battlesnake) {
-    info!("{} START", game.id);
+    info!(
+        "{} START {} game on the {} map",
+        game.id, game.source, game.map
+    );
 }
 
 pub fn end(game: &Game, _turn: &u32, _board: &Board, _me: &Battlesnake) {
-    info!("{} END", game.id);
+    info!(
+        "{} END {} game on the {} map",
+        game.id, game.source, game.map
+    );
 }
 
-pub fn get_move(game: &Game, _turn: &u32, board: &Board, me: &Battlesnake) -> &'static str {
-    let my_head = &me.head;
+pub const MOVES: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+// How much Cornelius cares about each part of a move, which differs between personalities
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Weights {
+    // Reward for moving where a shorter snake is sure to move next, 0 to leave them be
+    pub aggression: i32,
+}
+
+impl Weights {
+    pub const CAUTIOUS: Weights = Weights { aggression: 0 };
+}
 
-    let possible_moves: HashMap<_, _> = vec![
-        ("up", value_of_move(&my_head.up(), board, me)),
-        ("down", value_of_move(&my_head.down(), board, me)),
-        ("left", value_of_move(&my_head.left(), board, me)),
-        ("right", value_of_move(&my_head.right(), board, me)),
-    ]
-    .into_iter()
-    .collect();
+impl Default for Weights {
+    fn default() -> Weights {
+        Weights::CAUTIOUS
+    }
+}
 
-    let chosen = possible_moves.iter().max_by(|a, b| a.1.cmp(b.1)).unwrap().0;
+pub fn get_move(game: &Game, _turn: &u32, board: &Board, me: &Battlesnake) -> Direction {
+    let deadline = search::deadline(game, Instant::now());
+    let chosen = if solo::is_solo(board, me) {
+        solo::best_move(board, me)
+    } else if duel::is_duel(board, me) {
+        duel::best_move(board, me, deadline)
+    } else {
+        search::best_move(&search::Paranoid, board, me, deadline)
+    };
 
     info!("{} MOVE {}", game.id, chosen);
 
     chosen
 }
 
+// Scores each move Cornelius could make from where their head is now
+pub fn score_moves(board: &Board, me: &Battlesnake) -> Vec<(Direction, i32)> {
+    let grid = Grid::for_snake(board, me);
+    let options = move_options(board, &grid, me);
+    let hunger = hunger_at(me, meal_among(&options, board, &grid, me));
+    options
+        .iter()
+        .map(|(chosen, spot, arrivals)| {
+            let value = value_of_move(spot, board, &grid, me, arrivals, hunger);
+            (*chosen, value)
+        })
+        .collect()
+}
+
+// Each move Cornelius could make, with where it takes them and every cell they could get to
+// after it, so that scoring a move only has to search the board from there once
+pub fn move_options(
+    board: &Board,
+    grid: &Grid,
+    me: &Battlesnake,
+) -> Vec<(Direction, Coord, HashMap<Coord, Arrival>)> {
+    MOVES
+        .iter()
+        .map(|chosen| {
+            let spot = board.step(&me.head, *chosen);
+            (*chosen, spot, arrivals(&spot, board, grid, me))
+        })
+        .collect()
+}
+
+pub fn greedy_move(board: &Board, me: &Battlesnake) -> Direction {
+    score_moves(board, me)
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1))
+        .unwrap()
+        .0
+}
+
+// The value of a board for Cornelius is the value of the best move they could make on it
+pub fn value_of_board(board: &Board, me: &Battlesnake) -> i32 {
+    score_moves(board, me)
+        .into_iter()
+        .map(|(_, value)| value)
+        .max()
+        .unwrap()
+}
+
 #[cfg(test)]
 mod get_move_tests {
     use super::*;
@@ -65,12 +143,112 @@ mod get_move_tests {
         };
         let game = Game::default();
         let get_move = get_move(&game, &0, &board, &me);
-        assert_eq!(get_move, "down");
+        assert_eq!(get_move, Direction::Down);
+    }
+}
+
+// Cornelius lets rivals know when they're heading for their head. Shorter snakes would lose
+// the head-to-head, so they get taunted, and everyone else gets a warning.
+pub fn shout(board: &Board, me: &Battlesnake, chosen: Direction) -> Option<String> {
+    let spot = board.step(&me.head, chosen);
+    board
+        .snakes
+        .iter()
+        .filter(|snake| snake.id != me.id && !snake.is_teammate(me))
+        .filter(|snake| board.neighbours(&snake.head).contains(&spot))
+        .min_by_key(|snake| snake.length)
+        .map(|rival| {
+            if rival.length < me.length {
+                format!("Coming for you, {}!", rival.name)
+            } else {
+                format!("Mind your head, {}", rival.name)
+            }
+        })
+}
+
+#[cfg(test)]
+mod shout_tests {
+    use super::*;
+
+    fn snake(id: &str, name: &str, x: i32, length: i32) -> Battlesnake {
+        Battlesnake {
+            id: id.to_string(),
+            name: name.to_string(),
+            head: Coord { x, y: 5 },
+            body: (0..length).map(|y| Coord { x, y: 5 - y }).collect(),
+            length,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn taunts_shorter_snakes() {
+        let me = snake("me", "Cornelius", 3, 4);
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![
+                me.clone(),
+                snake("hettie", "Hettie", 5, 3),
+                snake("maud", "Maud", 9, 3),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            shout(&board, &me, Direction::Right),
+            Some("Coming for you, Hettie!".to_string())
+        );
+    }
+
+    #[test]
+    fn warns_longer_snakes() {
+        let me = snake("me", "Cornelius", 3, 4);
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), snake("hettie", "Hettie", 5, 5)],
+            ..Default::default()
+        };
+        assert_eq!(
+            shout(&board, &me, Direction::Right),
+            Some("Mind your head, Hettie".to_string())
+        );
+    }
+
+    #[test]
+    fn quiet_away_from_rivals() {
+        let me = snake("me", "Cornelius", 3, 4);
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), snake("hettie", "Hettie", 5, 3)],
+            ..Default::default()
+        };
+        assert_eq!(shout(&board, &me, Direction::Left), None);
+    }
+
+    #[test]
+    fn leaves_teammates_be() {
+        let me = Battlesnake {
+            squad: Some("corneliuses".to_string()),
+            ..snake("me", "Cornelius", 3, 4)
+        };
+        let hettie = Battlesnake {
+            squad: Some("corneliuses".to_string()),
+            ..snake("hettie", "Hettie", 5, 3)
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), hettie],
+            ..Default::default()
+        };
+        assert_eq!(shout(&board, &me, Direction::Right), None);
     }
 }
 
-fn spot_has_food(spot: &Coord, board: &Board) -> bool {
-    board.food.contains(&spot)
+fn spot_has_food(spot: &Coord, grid: &Grid) -> bool {
+    grid.has_food(spot)
 }
 
 #[cfg(test)]
@@ -80,6 +258,8 @@ mod spot_has_food_test {
     #[test]
     fn hazardous_spot_test() {
         let board = Board {
+            height: 11,
+            width: 11,
             food: vec![
                 Coord { x: 10, y: 0 },
                 Coord { x: 7, y: 6 },
@@ -88,12 +268,14 @@ mod spot_has_food_test {
             ..Default::default()
         };
         let spot = Coord { x: 7, y: 6 };
-        assert_eq!(spot_has_food(&spot, &board), true);
+        assert_eq!(spot_has_food(&spot, &Grid::new(&board)), true);
     }
 
     #[test]
     fn safe_spot_test() {
         let board = Board {
+            height: 11,
+            width: 11,
             food: vec![
                 Coord { x: 10, y: 0 },
                 Coord { x: 7, y: 6 },
@@ -102,12 +284,12 @@ mod spot_has_food_test {
             ..Default::default()
         };
         let spot = Coord { x: 3, y: 5 };
-        assert_eq!(spot_has_food(&spot, &board), false);
+        assert_eq!(spot_has_food(&spot, &Grid::new(&board)), false);
     }
 }
 
-fn spot_has_hazards(spot: &Coord, board: &Board) -> bool {
-    board.hazards.contains(spot)
+fn spot_has_hazards(spot: &Coord, grid: &Grid) -> bool {
+    grid.has_hazard(spot)
 }
 
 #[cfg(test)]
@@ -117,6 +299,8 @@ mod spot_has_hazards_test {
     #[test]
     fn hazardous_spot_test() {
         let board = Board {
+            height: 11,
+            width: 11,
             hazards: vec![
                 Coord { x: 0, y: 0 },
                 Coord { x: 0, y: 1 },
@@ -132,12 +316,14 @@ mod spot_has_hazards_test {
             ..Default::default()
         };
         let spot = Coord { x: 0, y: 5 };
-        assert!(spot_has_hazards(&spot, &board));
+        assert!(spot_has_hazards(&spot, &Grid::new(&board)));
     }
 
     #[test]
     fn safe_spot_test() {
         let board = Board {
+            height: 11,
+            width: 11,
             hazards: vec![
                 Coord { x: 0, y: 0 },
                 Coord { x: 0, y: 1 },
@@ -153,21 +339,14 @@ mod spot_has_hazards_test {
             ..Default::default()
         };
         let spot = Coord { x: 3, y: 5 };
-        assert!(!spot_has_hazards(&spot, &board));
+        assert!(!spot_has_hazards(&spot, &Grid::new(&board)));
     }
 }
 
-fn spot_has_snake(spot: &Coord, snakes: &[Battlesnake]) -> bool {
-    let mut snake_parts = vec![];
-    for snake in snakes {
-        snake_parts.push(snake.head);
-        snake_parts.append(&mut snake.body.clone());
-    }
-    if snake_parts.contains(spot) {
-        return true;
-    }
-
-    false
+// Whether a snake will still be in the spot next turn. Tails move out of the way
+// unless their snake just ate and has a stacked tail.
+fn spot_has_snake(spot: &Coord, grid: &Grid) -> bool {
+    grid.free_after(spot).map_or(false, |free| free > 1)
 }
 
 #[cfg(test)]
@@ -190,9 +369,14 @@ mod spot_has_snake_tests {
             body: vec![Coord { x: 0, y: 0 }, Coord { x: 1, y: 0 }],
             ..Default::default()
         };
-        let snakes = vec![hettie, me];
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie, me],
+            ..Default::default()
+        };
         let spot = Coord { x: 5, y: 7 };
-        assert!(!spot_has_snake(&spot, &snakes));
+        assert!(!spot_has_snake(&spot, &Grid::new(&board)));
     }
 
     #[test]
@@ -204,13 +388,18 @@ mod spot_has_snake_tests {
             body: vec![Coord { x: 3, y: 3 }, Coord { x: 3, y: 2 }],
             ..Default::default()
         };
-        let snakes = vec![hettie, me];
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie, me],
+            ..Default::default()
+        };
         let spot = Coord { x: 2, y: 3 };
-        assert!(spot_has_snake(&spot, &snakes));
+        assert!(spot_has_snake(&spot, &Grid::new(&board)));
     }
 
     #[test]
-    fn tail_in_spot() {
+    fn tail_leaves_spot() {
         let me = Battlesnake::default();
         let hettie = Battlesnake {
             name: "Hettie".to_string(),
@@ -218,9 +407,38 @@ mod spot_has_snake_tests {
             body: vec![Coord { x: 3, y: 3 }, Coord { x: 3, y: 2 }],
             ..Default::default()
         };
-        let snakes = vec![hettie, me];
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie, me],
+            ..Default::default()
+        };
+        let spot = Coord { x: 3, y: 2 };
+        assert!(!spot_has_snake(&spot, &Grid::new(&board)));
+    }
+
+    #[test]
+    fn tail_stays_after_eating() {
+        let me = Battlesnake::default();
+        let hettie = Battlesnake {
+            name: "Hettie".to_string(),
+            head: Coord { x: 2, y: 3 },
+            body: vec![
+                Coord { x: 2, y: 3 },
+                Coord { x: 3, y: 3 },
+                Coord { x: 3, y: 2 },
+                Coord { x: 3, y: 2 },
+            ],
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie, me],
+            ..Default::default()
+        };
         let spot = Coord { x: 3, y: 2 };
-        assert!(spot_has_snake(&spot, &snakes));
+        assert!(spot_has_snake(&spot, &Grid::new(&board)));
     }
 
     #[test]
@@ -239,9 +457,14 @@ mod spot_has_snake_tests {
             body: vec![Coord { x: 0, y: 0 }, Coord { x: 1, y: 0 }],
             ..Default::default()
         };
-        let snakes = vec![hettie, me];
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie, me],
+            ..Default::default()
+        };
         let spot = Coord { x: 0, y: 0 };
-        assert!(spot_has_snake(&spot, &snakes));
+        assert!(spot_has_snake(&spot, &Grid::new(&board)));
     }
 
     #[test]
@@ -260,34 +483,30 @@ mod spot_has_snake_tests {
             body: vec![Coord { x: 0, y: 0 }, Coord { x: 1, y: 0 }],
             ..Default::default()
         };
-        let snakes = vec![hettie, me];
-        let spot = Coord { x: 5, y: 5 };
-        assert!(spot_has_snake(&spot, &snakes));
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie, me],
+            ..Default::default()
+        };
+        let spot = Coord { x: 4, y: 5 };
+        assert!(spot_has_snake(&spot, &Grid::new(&board)));
     }
 }
 
-fn spot_might_have_snake(spot: &Coord, snakes: &[Battlesnake], me: &Battlesnake) -> bool {
-    let mut snake_parts = vec![];
-    for snake in snakes {
-        if snake.id != me.id && snake.length >= me.length {
-            let head = snake.head;
-
-            snake_parts.push(head.left());
-            snake_parts.push(head.right());
-            snake_parts.push(head.up());
-            snake_parts.push(head.down());
-        }
-    }
-    if snake_parts.contains(spot) {
-        return true;
-    }
+fn spot_might_have_snake(spot: &Coord, grid: &Grid) -> bool {
+    grid.might_have_snake(spot)
+}
 
-    false
+// How likely a shorter snake is to move into the spot next, as a percentage
+fn spot_might_have_prey(spot: &Coord, grid: &Grid) -> i32 {
+    grid.prey_odds(spot)
 }
 
 #[cfg(test)]
 mod spot_might_have_snake_tests {
     use super::*;
+    use crate::{Ruleset, RulesetName};
 
     #[test]
     fn no_snakes_in_spot() {
@@ -305,9 +524,14 @@ mod spot_might_have_snake_tests {
             length: 4,
             ..Default::default()
         };
-        let snakes = vec![hettie, me.clone()];
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie, me.clone()],
+            ..Default::default()
+        };
         let spot = Coord { x: 5, y: 7 };
-        assert!(!spot_might_have_snake(&spot, &snakes, &me));
+        assert!(!spot_might_have_snake(&spot, &Grid::for_snake(&board, &me)));
     }
 
     #[test]
@@ -327,9 +551,14 @@ mod spot_might_have_snake_tests {
             length: 4,
             ..Default::default()
         };
-        let snakes = vec![hettie];
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie],
+            ..Default::default()
+        };
         let spot = head.right();
-        assert!(spot_might_have_snake(&spot, &snakes, &me));
+        assert!(spot_might_have_snake(&spot, &Grid::for_snake(&board, &me)));
     }
 
     #[test]
@@ -349,9 +578,14 @@ mod spot_might_have_snake_tests {
             length: 4,
             ..Default::default()
         };
-        let snakes = vec![hettie];
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie],
+            ..Default::default()
+        };
         let spot = head.left();
-        assert!(spot_might_have_snake(&spot, &snakes, &me));
+        assert!(spot_might_have_snake(&spot, &Grid::for_snake(&board, &me)));
     }
 
     #[test]
@@ -371,9 +605,14 @@ mod spot_might_have_snake_tests {
             length: 3,
             ..Default::default()
         };
-        let snakes = vec![hettie];
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie],
+            ..Default::default()
+        };
         let spot = head.down();
-        assert!(spot_might_have_snake(&spot, &snakes, &me));
+        assert!(spot_might_have_snake(&spot, &Grid::for_snake(&board, &me)));
     }
 
     #[test]
@@ -393,9 +632,14 @@ mod spot_might_have_snake_tests {
             length: 3,
             ..Default::default()
         };
-        let snakes = vec![hettie];
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie],
+            ..Default::default()
+        };
         let spot = head.up();
-        assert!(spot_might_have_snake(&spot, &snakes, &me));
+        assert!(spot_might_have_snake(&spot, &Grid::for_snake(&board, &me)));
     }
 
     #[test]
@@ -415,9 +659,14 @@ mod spot_might_have_snake_tests {
             length: 3,
             ..Default::default()
         };
-        let snakes = vec![hettie];
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie],
+            ..Default::default()
+        };
         let spot = head.right();
-        assert!(!spot_might_have_snake(&spot, &snakes, &me));
+        assert!(!spot_might_have_snake(&spot, &Grid::for_snake(&board, &me)));
     }
 
     #[test]
@@ -429,47 +678,206 @@ mod spot_might_have_snake_tests {
             head,
             ..Default::default()
         };
-        let snakes = vec![me.clone()];
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        let spot = head.right();
+        assert!(!spot_might_have_snake(&spot, &Grid::for_snake(&board, &me)));
+    }
+
+    #[test]
+    fn larger_snake_head_across_the_edge() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            name: "CorneliusCodes".to_string(),
+            head: Coord { x: 7, y: 6 },
+            length: 3,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "HettieCodes".to_string(),
+            head: Coord { x: 10, y: 5 },
+            length: 4,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie],
+            ruleset: Ruleset {
+                name: RulesetName::Wrapped,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let spot = Coord { x: 0, y: 5 };
+        assert!(spot_might_have_snake(&spot, &Grid::for_snake(&board, &me)));
+    }
+
+    #[test]
+    fn teammate_head_next_to_spot() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            name: "CorneliusCodes".to_string(),
+            head: Coord { x: 7, y: 6 },
+            length: 3,
+            squad: Some("corns".to_string()),
+            ..Default::default()
+        };
+        let head = Coord { x: 3, y: 5 };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "HettieCodes".to_string(),
+            head,
+            length: 4,
+            squad: Some("corns".to_string()),
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![hettie],
+            ..Default::default()
+        };
         let spot = head.right();
-        assert!(!spot_might_have_snake(&spot, &snakes, &me));
+        assert!(!spot_might_have_snake(&spot, &Grid::for_snake(&board, &me)));
+    }
+
+    #[test]
+    fn constrictor_cannot_slip_into_a_body() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            name: "CorneliusCodes".to_string(),
+            head: Coord { x: 4, y: 6 },
+            body: vec![Coord { x: 4, y: 6 }, Coord { x: 4, y: 7 }],
+            length: 2,
+            ..Default::default()
+        };
+        let head = Coord { x: 3, y: 5 };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "HettieCodes".to_string(),
+            head,
+            body: vec![head, head.down(), Coord { x: 3, y: 3 }],
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), hettie],
+            ruleset: Ruleset {
+                name: RulesetName::Constrictor,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(!spot_might_have_snake(&head.down(), &Grid::for_snake(&board, &me)));
+        assert!(spot_might_have_snake(&head.up(), &Grid::for_snake(&board, &me)));
     }
 }
 
-fn remaining_space(spot: &Coord, board: &Board, me: &Battlesnake) -> i32 {
-    let available_spaces = check_spot_for_space(spot, board, me.length, [].to_vec());
-    available_spaces.len() as i32
+// Every cell Cornelius could reach alive from a spot, counting body segments that will
+// have moved on by the time they get there
+fn remaining_space(arrivals: &HashMap<Coord, Arrival>) -> i32 {
+    arrivals.len() as i32
+}
+
+// The first turn Cornelius could get to a cell, and the most health they could have left
+// when they get there, before eating anything on it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Arrival {
+    pub turn: u16,
+    pub health: i32,
 }
 
-fn check_spot_for_space(spot: &Coord, board: &Board, my_length: i32, mut available_spaces: Vec<Coord>) -> Vec<Coord> {
-    if my_length <= available_spaces.len() as i32 {
-            return available_spaces;
+// Each cell Cornelius can reach without starving, moving onto the spot next turn
+fn arrivals(
+    spot: &Coord,
+    board: &Board,
+    grid: &Grid,
+    me: &Battlesnake,
+) -> HashMap<Coord, Arrival> {
+    let reachable = |spot: &Coord, turn: u16| {
+        grid.free_after(spot)
+            .map_or(false, |free| free <= turn)
+    };
+
+    // Whether Cornelius can move onto the spot at all is up to valid_move
+    let mut arrivals = HashMap::new();
+    let start = board.wrap(*spot);
+    if !valid_move(&start, grid) || starves(me.health, &start, board, grid) {
+        return arrivals;
     }
-    if available_spaces.contains(spot) {
-        return available_spaces;
+
+    // Breadth first, so each cell is reached as early as Cornelius could get there.
+    // A cell is visited again when a longer way round leaves more health for going on.
+    let mut queue = VecDeque::new();
+    let health = me.health - health_cost(&start, board, grid);
+    arrivals.insert(start, Arrival { turn: 1, health });
+    queue.push_back((start, 1, health_after(health, &start, grid)));
+    while let Some((spot, turn, health)) = queue.pop_front() {
+        for neighbour in board.neighbours(&spot).iter() {
+            if !reachable(neighbour, turn + 1) || starves(health, neighbour, board, grid) {
+                continue;
+            }
+            let arrived = health - health_cost(neighbour, board, grid);
+            match arrivals.get_mut(neighbour) {
+                // Food only gets eaten the first time round
+                Some(_) if spot_has_food(neighbour, grid) => continue,
+                Some(arrival) if arrival.health >= arrived => continue,
+                Some(arrival) => arrival.health = arrived,
+                None => {
+                    arrivals.insert(
+                        *neighbour,
+                        Arrival {
+                            turn: turn + 1,
+                            health: arrived,
+                        },
+                    );
+                }
+            }
+            queue.push_back((*neighbour, turn + 1, health_after(arrived, neighbour, grid)));
+        }
     }
+    arrivals
+}
 
-    if valid_move(spot, board) {
-        available_spaces.push(*spot);
+// Health lost moving onto a spot, once for the turn and again for every hazard stacked there.
+// Food is eaten before the hazards can do any harm.
+fn health_cost(spot: &Coord, board: &Board, grid: &Grid) -> i32 {
+    if spot_has_food(spot, grid) {
+        1
+    } else {
+        1 + board.ruleset.settings.hazard_damage_per_turn * grid.hazards(spot)
+    }
+}
 
-        if valid_move(&spot.right(), board) {
-            available_spaces = check_spot_for_space(&spot.right(), board, my_length, available_spaces);
-        }
-        if valid_move(&spot.left(), board) {
-            available_spaces = check_spot_for_space(&spot.left(), board, my_length, available_spaces);
-        }
-        if valid_move(&spot.up(), board) {
-            available_spaces = check_spot_for_space(&spot.up(), board, my_length, available_spaces);
-        }
-        if valid_move(&spot.down(), board) {
-            available_spaces = check_spot_for_space(&spot.down(), board, my_length, available_spaces);
-        }
+fn health_after(health: i32, spot: &Coord, grid: &Grid) -> i32 {
+    if spot_has_food(spot, grid) {
+        rules::MAX_HEALTH
+    } else {
+        health
     }
-    available_spaces
+}
+
+// Whether moving onto a spot with the health given would finish Cornelius off
+fn starves(health: i32, spot: &Coord, board: &Board, grid: &Grid) -> bool {
+    !spot_has_food(spot, grid) && health <= health_cost(spot, board, grid)
 }
 
 #[cfg(test)]
 mod remaining_space {
     use super::*;
+    use crate::{Ruleset, RulesetName};
+
+    fn space(spot: &Coord, board: &Board, me: &Battlesnake) -> i32 {
+        remaining_space(&arrivals(spot, board, &Grid::for_snake(board, me), me))
+    }
 
     #[test]
     fn spot_is_big_enough_for_corney() {
@@ -490,11 +898,11 @@ mod remaining_space {
             ..Default::default()
         };
         let spot = Coord { x: 0, y: 5 };
-        assert_eq!(remaining_space(&spot, &board, &me), 4);
+        assert_eq!(space(&spot, &board, &me), 100);
     }
 
     #[test]
-    fn spot_is_too_small_for_corney() {
+    fn spot_opens_up_once_the_tail_moves() {
         let me = Battlesnake {
             name: "CorneliusCodes".to_string(),
             body: vec![
@@ -518,7 +926,42 @@ mod remaining_space {
             ..Default::default()
         };
         let spot = Coord { x: 2, y: 9 };
-        assert_eq!(remaining_space(&spot, &board, &me), 3);
+        // The head is left in the corner and the body is listed from the far end, so the
+        // segment at (3,9) is the tail and moves out of the way, leaving the board open
+        assert_eq!(space(&spot, &board, &me), 100);
+    }
+
+    #[test]
+    fn spot_is_walled_off_by_their_own_body() {
+        let me = Battlesnake {
+            name: "CorneliusCodes".to_string(),
+            head: Coord { x: 3, y: 9 },
+            body: vec![
+                Coord { x: 3, y: 9 },
+                Coord { x: 4, y: 9 },
+                Coord { x: 5, y: 9 },
+                Coord { x: 5, y: 8 },
+                Coord { x: 4, y: 8 },
+                Coord { x: 3, y: 8 },
+                Coord { x: 2, y: 8 },
+                Coord { x: 1, y: 8 },
+                Coord { x: 0, y: 8 },
+                Coord { x: 0, y: 7 },
+                Coord { x: 0, y: 6 },
+                Coord { x: 0, y: 5 },
+                Coord { x: 0, y: 4 },
+            ],
+            length: 13,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 10,
+            width: 10,
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        let spot = Coord { x: 2, y: 9 };
+        assert_eq!(space(&spot, &board, &me), 3);
     }
 
     #[test]
@@ -547,44 +990,917 @@ mod remaining_space {
             ..Default::default()
         };
         let spot = Coord { x: 2, y: 1 };
-        assert_eq!(remaining_space(&spot, &board, &me), 7);
-    }
-}
-
-fn spot_modifier(spot: &Coord, board: &Board, me: &Battlesnake) -> i32 {
-    let mut modifier = 0;
-    if spot_might_have_snake(spot, &board.snakes, &me) {
-        modifier -= 80;
-    }
-    if spot_has_food(spot, &board) {
-        modifier += 75;
-    } else if spot_has_hazards(spot, &board) {
-        let leftover_health = me.health - 14;
-        modifier -= 100 - leftover_health;
+        assert_eq!(space(&spot, &board, &me), 100);
     }
-    let spaces = remaining_space(spot, &board, &me);
-    if spaces >= me.length {
-        modifier += 50
-    } else {
-        modifier -= 80 - spaces
-    }
-    modifier
-}
-
-#[cfg(test)]
-mod spot_modifier_tests {
-    use super::*;
 
     #[test]
-    fn spot_with_hazards() {
-        let board = Board {
-            hazards: vec![
-                Coord { x: 2, y: 0 },
-                Coord { x: 2, y: 2 },
-                Coord { x: 2, y: 4 },
+    fn tail_will_be_gone_by_the_time_corney_gets_there() {
+        let me = Battlesnake {
+            name: "CorneliusCodes".to_string(),
+            head: Coord { x: 3, y: 9 },
+            body: vec![
+                Coord { x: 3, y: 9 },
+                Coord { x: 3, y: 8 },
                 Coord { x: 2, y: 8 },
-                Coord { x: 2, y: 10 },
+                Coord { x: 1, y: 8 },
+                Coord { x: 0, y: 8 },
             ],
+            length: 5,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 10,
+            width: 10,
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        let spot = Coord { x: 2, y: 9 };
+        assert_eq!(space(&spot, &board, &me), 100);
+    }
+
+    #[test]
+    fn constrictor_counts_the_whole_region() {
+        let me = Battlesnake {
+            name: "CorneliusCodes".to_string(),
+            body: vec![
+                Coord { x: 0, y: 8 },
+                Coord { x: 1, y: 8 },
+                Coord { x: 2, y: 8 },
+                Coord { x: 3, y: 8 },
+                Coord { x: 4, y: 8 },
+                Coord { x: 5, y: 8 },
+                Coord { x: 5, y: 9 },
+            ],
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 10,
+            width: 10,
+            snakes: vec![me.clone()],
+            ruleset: Ruleset {
+                name: RulesetName::Constrictor,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let spot = Coord { x: 2, y: 9 };
+        assert_eq!(space(&spot, &board, &me), 5);
+    }
+
+    #[test]
+    fn stacked_hazards_wall_off_the_rest_of_the_board() {
+        let me = Battlesnake {
+            name: "CorneliusCodes".to_string(),
+            head: Coord { x: 5, y: 1 },
+            body: vec![
+                Coord { x: 5, y: 1 },
+                Coord { x: 5, y: 0 },
+                Coord { x: 4, y: 0 },
+            ],
+            health: 20,
+            length: 3,
+            ..Default::default()
+        };
+        let mut board = Board {
+            height: 10,
+            width: 10,
+            hazards: (0..10).map(|x| Coord { x, y: 3 }).collect(),
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        let spot = Coord { x: 5, y: 2 };
+        // One hazard hurts, but Cornelius can make it through
+        assert!(space(&spot, &board, &me) > 40);
+        board.hazards.extend((0..10).map(|x| Coord { x, y: 3 }));
+        assert_eq!(space(&spot, &board, &me), 30);
+    }
+
+    #[test]
+    fn food_past_the_hazards_keeps_cornelius_going() {
+        let me = Battlesnake {
+            name: "CorneliusCodes".to_string(),
+            head: Coord { x: 5, y: 1 },
+            body: vec![
+                Coord { x: 5, y: 1 },
+                Coord { x: 5, y: 0 },
+                Coord { x: 4, y: 0 },
+            ],
+            health: 20,
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 10,
+            width: 10,
+            food: vec![Coord { x: 5, y: 3 }],
+            hazards: (0..10).flat_map(|x| vec![Coord { x, y: 4 }; 2]).collect(),
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        let spot = Coord { x: 5, y: 2 };
+        assert_eq!(space(&spot, &board, &me), 100);
+    }
+}
+
+fn spot_modifier(
+    spot: &Coord,
+    board: &Board,
+    grid: &Grid,
+    me: &Battlesnake,
+    arrivals: &HashMap<Coord, Arrival>,
+    hunger: Hunger,
+) -> i32 {
+    let mut modifier = 0;
+    if spot_might_have_snake(spot, grid) {
+        modifier -= 80;
+    }
+    modifier += board.weights.aggression * spot_might_have_prey(spot, grid) / 100;
+    let meal = closest_food(arrivals, board, grid, me);
+    if spot_has_food(spot, grid) {
+        modifier += match hunger {
+            Hunger::Ignore => 0,
+            Hunger::Opportunistic => 75,
+            Hunger::MustEat => 150,
+        };
+    } else {
+        modifier += food_modifier(hunger, meal, me);
+        if spot_has_hazards(spot, grid) {
+            let damage = board.ruleset.settings.hazard_damage_per_turn * grid.hazards(spot);
+            modifier -= 100 - (me.health - damage);
+        }
+    }
+    let spaces = remaining_space(arrivals);
+    if spaces >= me.length {
+        modifier += 50
+    } else {
+        modifier -= 80 - spaces
+    }
+    if board.ruleset.constrictor() {
+        modifier += constrictor_modifier(spaces, board)
+    }
+    modifier += territory_modifier(spot, board, grid, me);
+    modifier += trap_modifier(spot, grid);
+    modifier
+}
+
+// Nothing moves out of the way in constrictor, so room counts for more, up to 50 for the
+// whole board whatever size it is
+fn constrictor_modifier(spaces: i32, board: &Board) -> i32 {
+    let cells = board.width * board.height;
+    if cells > 0 {
+        50 * spaces / cells
+    } else {
+        0
+    }
+}
+
+// Worth up to 50 for owning the whole board, and as much off for an opponent owning it
+fn territory_modifier(spot: &Coord, board: &Board, grid: &Grid, me: &Battlesnake) -> i32 {
+    let cells = board.width * board.height;
+    match territory(spot, board, grid, me) {
+        Some((mine, theirs)) if cells > 0 => 50 * (mine - theirs) / cells,
+        _ => 0,
+    }
+}
+
+// Races Cornelius from the spot they're moving to against the split the grid worked out
+// for everyone else, so only Cornelius' side has to be searched again for each spot.
+// Longer snakes win ties, and getting somewhere first cuts off whatever is behind it.
+// Returns the cells Cornelius gets and the most any opponent keeps, or None without opponents.
+pub fn territory(spot: &Coord, board: &Board, grid: &Grid, me: &Battlesnake) -> Option<(i32, i32)> {
+    let rivals: Vec<usize> = board
+        .snakes
+        .iter()
+        .enumerate()
+        .filter(|(_, snake)| snake.id != me.id && !snake.is_teammate(me))
+        .map(|(owner, _)| owner)
+        .collect();
+    if rivals.is_empty() {
+        return None;
+    }
+
+    // Whether Cornelius gets a cell they reach on the turn given
+    let outcome = |spot: &Coord, turn: u16| match grid.claim(spot) {
+        Some(claim) if claim.turn < turn => Ordering::Less,
+        Some(claim) if claim.turn == turn => me.length.cmp(&claim.length),
+        _ => Ordering::Greater,
+    };
+
+    // Only cells on the board are ever reached
+    let index = |spot: &Coord| (spot.y * board.width + spot.x) as usize;
+    let mut reached = vec![false; (board.width * board.height).max(0) as usize];
+    let mut taken = vec![0; board.snakes.len()];
+    let mut mine = 0;
+    let mut queue = VecDeque::new();
+    let start = board.wrap(*spot);
+    if valid_move(&start, grid) {
+        reached[index(&start)] = true;
+        queue.push_back((start, 1));
+    }
+    while let Some((spot, turn)) = queue.pop_front() {
+        let won = outcome(&spot, turn);
+        if won == Ordering::Less {
+            continue;
+        }
+        // Even a tie means nobody else gets it
+        if let Some(owner) = grid.claim(&spot).and_then(|claim| claim.owner) {
+            taken[owner] += 1;
+        }
+        if won == Ordering::Equal {
+            continue;
+        }
+        mine += 1;
+        let next = turn + 1;
+        for neighbour in board.neighbours(&spot).iter() {
+            if grid.free_after(neighbour).map_or(true, |free| free > next) {
+                continue;
+            }
+            if !reached[index(neighbour)] {
+                reached[index(neighbour)] = true;
+                queue.push_back((*neighbour, next));
+            }
+        }
+    }
+
+    let theirs = rivals
+        .iter()
+        .map(|owner| grid.claimed(*owner) - taken[*owner])
+        .max()
+        .unwrap_or(0);
+    Some((mine, theirs))
+}
+
+#[cfg(test)]
+mod territory_tests {
+    use super::*;
+
+    #[test]
+    fn closer_cells_belong_to_cornelius() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 1, y: 5 },
+            body: vec![Coord { x: 1, y: 5 }, Coord { x: 0, y: 5 }],
+            length: 2,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 9, y: 5 },
+            body: vec![Coord { x: 9, y: 5 }, Coord { x: 10, y: 5 }],
+            length: 2,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), hettie],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        let (toward, _) = territory(&Coord { x: 2, y: 5 }, &board, &grid, &me).unwrap();
+        let (away, _) = territory(&Coord { x: 1, y: 6 }, &board, &grid, &me).unwrap();
+        assert!(toward > away);
+    }
+
+    #[test]
+    fn longer_snakes_win_ties() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 4, y: 0 },
+            body: vec![Coord { x: 4, y: 0 }],
+            length: 5,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 2, y: 0 },
+            body: vec![Coord { x: 2, y: 0 }],
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 1,
+            width: 7,
+            snakes: vec![me.clone(), hettie],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        // Both get to the middle together, and it goes to Cornelius for being longer
+        let spot = Coord { x: 3, y: 0 };
+        assert_eq!(territory(&spot, &board, &grid, &me), Some((4, 2)));
+    }
+
+    #[test]
+    fn nobody_to_compete_with() {
+        let me = Battlesnake::default();
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        assert_eq!(territory(&Coord { x: 1, y: 0 }, &board, &grid, &me), None);
+    }
+}
+
+// Worth this much for every opponent a move leaves without room to fit their body
+const TRAP_BONUS: i32 = 60;
+
+// Rewards moves that leave an opponent with less room than they are long,
+// like pinning them against a wall or closing off the corridor they are in
+fn trap_modifier(spot: &Coord, grid: &Grid) -> i32 {
+    TRAP_BONUS * grid.trapped(spot)
+}
+
+#[cfg(test)]
+mod trap_modifier_tests {
+    use super::*;
+
+    #[test]
+    fn pins_hettie_against_the_wall() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 1, y: 6 },
+            body: vec![
+                Coord { x: 1, y: 6 },
+                Coord { x: 1, y: 5 },
+                Coord { x: 1, y: 4 },
+                Coord { x: 1, y: 3 },
+                Coord { x: 1, y: 2 },
+            ],
+            length: 5,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 0, y: 5 },
+            body: vec![
+                Coord { x: 0, y: 5 },
+                Coord { x: 0, y: 4 },
+                Coord { x: 0, y: 3 },
+                Coord { x: 0, y: 2 },
+            ],
+            length: 4,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), hettie],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        assert_eq!(trap_modifier(&me.head.left(), &grid), TRAP_BONUS);
+        // Running alongside leaves her the whole wall to escape up
+        assert_eq!(trap_modifier(&me.head.up(), &grid), 0);
+    }
+
+    #[test]
+    fn closes_off_the_corridor() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 5, y: 1 },
+            body: vec![
+                Coord { x: 5, y: 1 },
+                Coord { x: 4, y: 1 },
+                Coord { x: 3, y: 1 },
+                Coord { x: 2, y: 1 },
+                Coord { x: 1, y: 1 },
+                Coord { x: 0, y: 1 },
+            ],
+            length: 6,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 3, y: 0 },
+            body: vec![
+                Coord { x: 3, y: 0 },
+                Coord { x: 2, y: 0 },
+                Coord { x: 1, y: 0 },
+                Coord { x: 0, y: 0 },
+            ],
+            length: 4,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), hettie],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        assert_eq!(trap_modifier(&me.head.down(), &grid), TRAP_BONUS);
+        assert_eq!(trap_modifier(&me.head.right(), &grid), 0);
+        assert_eq!(trap_modifier(&me.head.up(), &grid), 0);
+    }
+
+    #[test]
+    fn hettie_is_stuck_whatever_cornelius_does() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 3, y: 5 },
+            body: vec![
+                Coord { x: 3, y: 5 },
+                Coord { x: 4, y: 5 },
+                Coord { x: 5, y: 5 },
+            ],
+            length: 3,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 0, y: 5 },
+            body: vec![
+                Coord { x: 0, y: 5 },
+                Coord { x: 0, y: 4 },
+                Coord { x: 0, y: 3 },
+                Coord { x: 0, y: 2 },
+            ],
+            length: 4,
+            ..Default::default()
+        };
+        // Maud has Hettie boxed in against the wall
+        let maud = Battlesnake {
+            id: "maud".to_string(),
+            name: "Maud".to_string(),
+            head: Coord { x: 2, y: 7 },
+            body: vec![
+                Coord { x: 2, y: 7 },
+                Coord { x: 1, y: 7 },
+                Coord { x: 0, y: 7 },
+                Coord { x: 0, y: 6 },
+                Coord { x: 1, y: 6 },
+                Coord { x: 1, y: 5 },
+                Coord { x: 1, y: 4 },
+                Coord { x: 1, y: 3 },
+                Coord { x: 1, y: 2 },
+            ],
+            length: 9,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), hettie, maud],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        for chosen in MOVES.iter() {
+            let spot = board.step(&me.head, *chosen);
+            assert_eq!(trap_modifier(&spot, &grid), 0);
+        }
+    }
+
+    #[test]
+    fn hettie_has_room_to_spare() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 5, y: 4 },
+                Coord { x: 5, y: 3 },
+            ],
+            length: 3,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 7, y: 6 },
+            body: vec![
+                Coord { x: 7, y: 6 },
+                Coord { x: 7, y: 7 },
+                Coord { x: 7, y: 8 },
+            ],
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), hettie],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        for chosen in MOVES.iter() {
+            let spot = board.step(&me.head, *chosen);
+            assert_eq!(trap_modifier(&spot, &grid), 0);
+        }
+    }
+}
+
+// How badly Cornelius needs to eat, going by their health and the food they can get to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Hunger {
+    // Plenty of health left, so food is only in the way
+    Ignore,
+    // Eats whatever food is on the way
+    Opportunistic,
+    // Starves unless they head straight for food
+    MustEat,
+}
+
+impl fmt::Display for Hunger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mode = match self {
+            Hunger::Ignore => "ignore food",
+            Hunger::Opportunistic => "opportunistic",
+            Hunger::MustEat => "must eat now",
+        };
+        write!(f, "{}", mode)
+    }
+}
+
+// Cornelius stops ignoring food once their health drops to this
+pub const PECKISH: i32 = 80;
+// Health Cornelius wants left over when they get to food, in case the way gets blocked
+pub const HUNGER_MARGIN: i32 = 10;
+
+// How hungry Cornelius is this turn, worked out once from where their head is
+// so that every move gets scored the same way
+pub fn hunger(board: &Board, me: &Battlesnake) -> Hunger {
+    hunger_on(board, &Grid::for_snake(board, me), me)
+}
+
+fn hunger_on(board: &Board, grid: &Grid, me: &Battlesnake) -> Hunger {
+    hunger_at(me, best_meal(board, grid, me))
+}
+
+// The best meal any of Cornelius' moves leads to
+pub fn best_meal(board: &Board, grid: &Grid, me: &Battlesnake) -> Option<Arrival> {
+    meal_among(&move_options(board, grid, me), board, grid, me)
+}
+
+fn meal_among(
+    options: &[(Direction, Coord, HashMap<Coord, Arrival>)],
+    board: &Board,
+    grid: &Grid,
+    me: &Battlesnake,
+) -> Option<Arrival> {
+    options
+        .iter()
+        .filter_map(|(_, _, arrivals)| closest_food(arrivals, board, grid, me))
+        .max_by_key(|meal| (meal.health, Reverse(meal.turn)))
+}
+
+fn hunger_at(me: &Battlesnake, meal: Option<Arrival>) -> Hunger {
+    let leftover = meal.map_or(me.health, |meal| meal.health);
+    if leftover <= HUNGER_MARGIN {
+        Hunger::MustEat
+    } else if me.health <= PECKISH {
+        Hunger::Opportunistic
+    } else {
+        Hunger::Ignore
+    }
+}
+
+#[cfg(test)]
+mod hunger_tests {
+    use super::*;
+    use crate::{Ruleset, RulesetSettings};
+
+    fn cornelius(health: i32) -> Battlesnake {
+        Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 4, y: 5 },
+                Coord { x: 3, y: 5 },
+            ],
+            health,
+            length: 3,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn full_snakes_ignore_food() {
+        let me = cornelius(95);
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 5, y: 7 }],
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        assert_eq!(hunger(&board, &me), Hunger::Ignore);
+    }
+
+    #[test]
+    fn peckish_snakes_eat_on_the_way() {
+        let me = cornelius(60);
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 5, y: 7 }],
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        assert_eq!(hunger(&board, &me), Hunger::Opportunistic);
+    }
+
+    #[test]
+    fn far_away_food_must_be_eaten_now() {
+        let me = cornelius(20);
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 10, y: 0 }],
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        assert_eq!(hunger(&board, &me), Hunger::MustEat);
+    }
+
+    #[test]
+    fn hazards_on_the_way_make_food_urgent() {
+        let me = cornelius(40);
+        let mut board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 5, y: 8 }],
+            hazards: vec![],
+            snakes: vec![me.clone()],
+            ruleset: Ruleset {
+                settings: RulesetSettings {
+                    hazard_damage_per_turn: 14,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(hunger(&board, &me), Hunger::Opportunistic);
+        // Every way there crosses the hazards
+        board.hazards = (0..11).map(|x| Coord { x, y: 6 }).collect();
+        board.hazards.extend((0..11).map(|x| Coord { x, y: 7 }));
+        board.hazards.extend((0..11).map(|x| Coord { x, y: 4 }));
+        board.hazards.push(Coord { x: 6, y: 5 });
+        assert_eq!(hunger(&board, &me), Hunger::MustEat);
+    }
+
+    #[test]
+    fn every_move_is_as_hungry_as_the_turn() {
+        // Only the way left gets to the food with more than the margin to spare
+        let head = Coord { x: 11, y: 1 };
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head,
+            body: vec![head, head.down()],
+            health: 21,
+            length: 2,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 3,
+            width: 23,
+            food: vec![Coord { x: 1, y: 1 }],
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        assert_eq!(hunger(&board, &me), Hunger::Opportunistic);
+        assert_eq!(greedy_move(&board, &me), Direction::Left);
+    }
+
+    #[test]
+    fn nothing_to_eat() {
+        assert_eq!(hunger_at(&cornelius(100), None), Hunger::Ignore);
+        assert_eq!(hunger_at(&cornelius(50), None), Hunger::Opportunistic);
+        assert_eq!(hunger_at(&cornelius(5), None), Hunger::MustEat);
+    }
+}
+
+// Pulls Cornelius toward the closest food they can get to before anyone else,
+// harder the hungrier they are and the closer it is
+fn food_modifier(hunger: Hunger, meal: Option<Arrival>, me: &Battlesnake) -> i32 {
+    match (hunger, meal) {
+        (Hunger::Opportunistic, Some(meal)) => (100 - me.health).max(0) / i32::from(meal.turn),
+        (Hunger::MustEat, Some(meal)) => 150 / i32::from(meal.turn),
+        _ => 0,
+    }
+}
+
+// The food Cornelius wins the race to with the most health left, if there is any.
+// Getting there together only wins for the longer snake.
+fn closest_food(
+    arrivals: &HashMap<Coord, Arrival>,
+    board: &Board,
+    grid: &Grid,
+    me: &Battlesnake,
+) -> Option<Arrival> {
+    board
+        .food
+        .iter()
+        .filter_map(|food| {
+            let mine = *arrivals.get(food)?;
+            match grid.rival_arrival(food) {
+                Some((theirs, _)) if theirs < mine.turn => None,
+                Some((theirs, length)) if theirs == mine.turn && length >= me.length => None,
+                _ => Some(mine),
+            }
+        })
+        .max_by_key(|meal| (meal.health, Reverse(meal.turn)))
+}
+
+#[cfg(test)]
+mod closest_food_tests {
+    use super::*;
+
+    fn meal_from(spot: &Coord, board: &Board, grid: &Grid, me: &Battlesnake) -> Option<Arrival> {
+        closest_food(&arrivals(spot, board, grid, me), board, grid, me)
+    }
+
+    #[test]
+    fn heads_for_food_further_away() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 5, y: 4 },
+                Coord { x: 5, y: 3 },
+            ],
+            health: 40,
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 9, y: 5 }],
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        let right = meal_from(&me.head.right(), &board, &grid, &me);
+        let left = meal_from(&me.head.left(), &board, &grid, &me);
+        assert_eq!(right.map(|meal| meal.turn), Some(4));
+        assert_eq!(left.map(|meal| meal.turn), Some(8));
+        assert!(
+            food_modifier(Hunger::Opportunistic, right, &me)
+                > food_modifier(Hunger::Opportunistic, left, &me)
+        );
+    }
+
+    #[test]
+    fn leaves_food_hettie_gets_to_first() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 5, y: 4 },
+                Coord { x: 5, y: 3 },
+            ],
+            health: 40,
+            length: 3,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 9, y: 6 },
+            body: vec![
+                Coord { x: 9, y: 6 },
+                Coord { x: 9, y: 7 },
+                Coord { x: 9, y: 8 },
+            ],
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 9, y: 5 }, Coord { x: 1, y: 5 }],
+            snakes: vec![me.clone(), hettie],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        let right = meal_from(&me.head.right(), &board, &grid, &me);
+        let left = meal_from(&me.head.left(), &board, &grid, &me);
+        assert_eq!(right.map(|meal| meal.turn), Some(8));
+        assert_eq!(left.map(|meal| meal.turn), Some(4));
+    }
+
+    #[test]
+    fn walks_around_hazards() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 5, y: 4 },
+                Coord { x: 5, y: 3 },
+            ],
+            health: 40,
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 8, y: 6 }],
+            hazards: vec![Coord { x: 7, y: 5 }, Coord { x: 8, y: 6 }],
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        let meal = Arrival {
+            turn: 4,
+            health: 36,
+        };
+        assert_eq!(
+            meal_from(&me.head.right(), &board, &grid, &me),
+            Some(meal)
+        );
+    }
+
+    #[test]
+    fn full_snakes_are_not_pulled() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 5, y: 4 },
+                Coord { x: 5, y: 3 },
+            ],
+            health: 100,
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 9, y: 5 }],
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        let grid = Grid::for_snake(&board, &me);
+        let meal = meal_from(&me.head.right(), &board, &grid, &me);
+        assert_eq!(hunger_at(&me, meal), Hunger::Ignore);
+        assert_eq!(food_modifier(Hunger::Ignore, meal, &me), 0);
+    }
+}
+
+#[cfg(test)]
+mod spot_modifier_tests {
+    use super::*;
+    use crate::{Ruleset, RulesetName, RulesetSettings};
+
+    // As hungry as Cornelius is this turn, the way score_moves does it
+    fn modifier(spot: &Coord, board: &Board, me: &Battlesnake) -> i32 {
+        let grid = Grid::for_snake(board, me);
+        let arrivals = arrivals(spot, board, &grid, me);
+        spot_modifier(spot, board, &grid, me, &arrivals, hunger_on(board, &grid, me))
+    }
+
+    #[test]
+    fn spot_with_hazards() {
+        let board = Board {
+            height: 11,
+            width: 11,
+            hazards: vec![
+                Coord { x: 2, y: 0 },
+                Coord { x: 2, y: 2 },
+                Coord { x: 2, y: 4 },
+                Coord { x: 2, y: 8 },
+                Coord { x: 2, y: 10 },
+            ],
+            ..Default::default()
+        };
+        let me = Battlesnake {
+            name: "CorneliusCodes".to_string(),
+            health: 75,
+            ..Default::default()
+        };
+        let spot = Coord { x: 2, y: 4 };
+        assert_eq!(modifier(&spot, &board, &me), 11);
+    }
+
+    #[test]
+    fn spot_with_stronger_hazards() {
+        let board = Board {
+            height: 11,
+            width: 11,
+            hazards: vec![Coord { x: 2, y: 4 }],
+            ruleset: Ruleset {
+                settings: RulesetSettings {
+                    hazard_damage_per_turn: 30,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
             ..Default::default()
         };
         let me = Battlesnake {
@@ -593,7 +1909,7 @@ mod spot_modifier_tests {
             ..Default::default()
         };
         let spot = Coord { x: 2, y: 4 };
-        assert_eq!(spot_modifier(&spot, &board, &me), 11);
+        assert_eq!(modifier(&spot, &board, &me), -5);
     }
 
     #[test]
@@ -607,16 +1923,54 @@ mod spot_modifier_tests {
             ..Default::default()
         };
         let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone(), hettie],
+            ..Default::default()
+        };
+        let spot = Coord { x: 3, y: 6 };
+        // Hettie gets everywhere else first, too
+        assert_eq!(modifier(&spot, &board, &me), -79);
+    }
+
+    #[test]
+    fn spot_where_smaller_snakes_may_soon_be() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            length: 5,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "HettieCodes".to_string(),
+            head: Coord { x: 3, y: 5 },
+            body: vec![
+                Coord { x: 3, y: 5 },
+                Coord { x: 3, y: 4 },
+                Coord { x: 3, y: 3 },
+            ],
+            length: 3,
+            ..Default::default()
+        };
+        let mut board = Board {
+            height: 11,
+            width: 11,
             snakes: vec![me.clone(), hettie],
             ..Default::default()
         };
         let spot = Coord { x: 3, y: 6 };
-        assert_eq!(spot_modifier(&spot, &board, &me), -30);
+        let cautious = modifier(&spot, &board, &me);
+        board.weights = Weights { aggression: 60 };
+        let aggressive = modifier(&spot, &board, &me);
+        // Hettie has three ways to go
+        assert_eq!(aggressive - cautious, 19);
     }
 
     #[test]
     fn spot_with_food() {
         let board = Board {
+            height: 11,
+            width: 11,
             food: vec![
                 Coord { x: 3, y: 0 },
                 Coord { x: 2, y: 6 },
@@ -630,12 +1984,14 @@ mod spot_modifier_tests {
             ..Default::default()
         };
         let spot = Coord { x: 2, y: 6 };
-        assert_eq!(spot_modifier(&spot, &board, &me), 125);
+        assert_eq!(modifier(&spot, &board, &me), 125);
     }
 
     #[test]
     fn spot_with_food_and_hazard() {
         let board = Board {
+            height: 11,
+            width: 11,
             food: vec![
                 Coord { x: 2, y: 4 },
                 Coord { x: 2, y: 6 },
@@ -656,37 +2012,70 @@ mod spot_modifier_tests {
             ..Default::default()
         };
         let spot = Coord { x: 2, y: 4 };
-        assert_eq!(spot_modifier(&spot, &board, &me), 125);
+        assert_eq!(modifier(&spot, &board, &me), 125);
     }
-}
 
-fn valid_move(spot: &Coord, board: &Board) -> bool {
-    match spot {
-        Coord { y: -1, .. } => false,
-        Coord { x: -1, .. } => false,
-        Coord { y, .. } if y == &board.width => false,
-        Coord { x, .. } if x == &board.height => false,
-        spot if spot_has_snake(spot, &board.snakes) => false,
-        _ => true,
+    #[test]
+    fn constrictor_room_does_not_drown_out_the_rest() {
+        let head = Coord { x: 12, y: 12 };
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head,
+            body: vec![head, head.down(), head.down().down()],
+            health: 100,
+            length: 3,
+            ..Default::default()
+        };
+        let mut board = Board {
+            height: 25,
+            width: 25,
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        let standard = modifier(&head.up(), &board, &me);
+        board.ruleset.name = RulesetName::Constrictor;
+        let constrictor = modifier(&head.up(), &board, &me);
+        assert!(constrictor > standard);
+        assert!(constrictor - standard <= 50);
     }
 }
 
+pub fn valid_move(spot: &Coord, grid: &Grid) -> bool {
+    !grid.is_wall(spot) && !spot_has_snake(spot, grid)
+}
+
 // Returns the potential value of the move Cornelius
-fn value_of_move(spot: &Coord, board: &Board, me: &Battlesnake) -> i32 {
+fn value_of_move(
+    spot: &Coord,
+    board: &Board,
+    grid: &Grid,
+    me: &Battlesnake,
+    arrivals: &HashMap<Coord, Arrival>,
+    hunger: Hunger,
+) -> i32 {
+    let spot = &board.wrap(*spot);
     let base_value = match spot {
-        spot if spot_has_snake(spot, &board.snakes) => -99, // Bite someone else before you bite the dust!
-        spot if !valid_move(&spot, &board) => -100,
+        spot if spot_has_snake(spot, grid) => -99, // Bite someone else before you bite the dust!
+        spot if !valid_move(spot, grid) => -100,
+        spot if starves(me.health, spot, board, grid) => -100,
         Coord { y: 0, .. } => 60,
         Coord { x: 0, .. } => 60,
         _ => 100,
     };
 
-    base_value + spot_modifier(spot, &board, &me)
+    base_value + spot_modifier(spot, board, grid, me, arrivals, hunger)
 }
 
 #[cfg(test)]
 mod value_of_move_tests {
     use super::*;
+    use crate::{Ruleset, RulesetName, RulesetSettings, SquadSettings};
+
+    fn value_of(spot: &Coord, board: &Board, me: &Battlesnake) -> i32 {
+        let grid = Grid::for_snake(board, me);
+        let arrivals = arrivals(spot, board, &grid, me);
+        value_of_move(spot, board, &grid, me, &arrivals, hunger_on(board, &grid, me))
+    }
 
     // Wall Tests
     #[test]
@@ -700,9 +2089,10 @@ mod value_of_move_tests {
             food: vec![],
             hazards: vec![],
             snakes: vec![me.clone()],
+            ..Default::default()
         };
         let spot = Coord { x: -1, y: 5 };
-        let valid_move = value_of_move(&spot, &board, &me);
+        let valid_move = value_of(&spot, &board, &me);
         assert_eq!(valid_move, -180);
     }
 
@@ -717,9 +2107,10 @@ mod value_of_move_tests {
             food: vec![],
             hazards: vec![],
             snakes: vec![me.clone()],
+            ..Default::default()
         };
         let spot = Coord { x: 10, y: 5 };
-        let valid_move = value_of_move(&spot, &board, &me);
+        let valid_move = value_of(&spot, &board, &me);
         assert_eq!(valid_move, -180);
     }
 
@@ -734,9 +2125,10 @@ mod value_of_move_tests {
             food: vec![],
             hazards: vec![],
             snakes: vec![me.clone()],
+            ..Default::default()
         };
         let spot = Coord { x: 5, y: 10 };
-        let valid_move = value_of_move(&spot, &board, &me);
+        let valid_move = value_of(&spot, &board, &me);
         assert_eq!(valid_move, -180);
     }
 
@@ -751,12 +2143,33 @@ mod value_of_move_tests {
             food: vec![],
             hazards: vec![],
             snakes: vec![me.clone()],
+            ..Default::default()
         };
         let spot = Coord { x: 5, y: -1 };
-        let valid_move = value_of_move(&spot, &board, &me);
+        let valid_move = value_of(&spot, &board, &me);
         assert_eq!(valid_move, -180);
     }
 
+    #[test]
+    fn head_will_wrap_past_left_wall() {
+        let me = Battlesnake {
+            ..Default::default()
+        };
+        let board = Board {
+            width: 10,
+            height: 10,
+            snakes: vec![me.clone()],
+            ruleset: Ruleset {
+                name: RulesetName::Wrapped,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let spot = Coord { x: -1, y: 5 };
+        let valid_move = value_of(&spot, &board, &me);
+        assert_eq!(valid_move, 150);
+    }
+
     // Collision Tests
 
     #[test]
@@ -771,9 +2184,10 @@ mod value_of_move_tests {
             food: vec![],
             hazards: vec![],
             snakes: vec![me.clone()],
+            ..Default::default()
         };
-        let spot = Coord { x: 5, y: 5 };
-        let valid_move = value_of_move(&spot, &board, &me);
+        let spot = Coord { x: 5, y: 4 };
+        let valid_move = value_of(&spot, &board, &me);
         assert_eq!(valid_move, -179);
     }
 
@@ -786,14 +2200,72 @@ mod value_of_move_tests {
             ..Default::default()
         };
         let board = Board {
+            height: 11,
+            width: 11,
             snakes: vec![hettie, me.clone()],
             ..Default::default()
         };
-        let spot = Coord { x: 4, y: 2 };
-        let valid_move = value_of_move(&spot, &board, &me);
+        let spot = Coord { x: 3, y: 2 };
+        let valid_move = value_of(&spot, &board, &me);
         assert_eq!(valid_move, -179);
     }
 
+    #[test]
+    fn follow_my_tail() {
+        let head = Coord { x: 5, y: 5 };
+        let me = Battlesnake {
+            head,
+            body: vec![head, head.up(), head.up().left(), head.left()],
+            length: 4,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        let spot = head.left();
+        let valid_move = value_of(&spot, &board, &me);
+        assert_eq!(valid_move, 150);
+    }
+
+    #[test]
+    fn slither_through_teammate() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            squad: Some("corns".to_string()),
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            body: vec![Coord { x: 3, y: 2 }, Coord { x: 4, y: 2 }],
+            squad: Some("corns".to_string()),
+            ..Default::default()
+        };
+        let board = Board {
+            height: 10,
+            width: 10,
+            snakes: vec![hettie, me.clone()],
+            ruleset: Ruleset {
+                name: RulesetName::Squad,
+                settings: RulesetSettings {
+                    squad: SquadSettings {
+                        allow_body_collisions: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let spot = Coord { x: 4, y: 2 };
+        let valid_move = value_of(&spot, &board, &me);
+        assert_eq!(valid_move, 150);
+    }
+
     #[test]
     fn potential_snake_head() {
         let me = Battlesnake::default();
@@ -807,11 +2279,13 @@ mod value_of_move_tests {
         };
         let spot = head.right();
         let board = Board {
+            height: 11,
+            width: 11,
             snakes: vec![me.clone(), hettie],
             ..Default::default()
         };
-        let valid_move = value_of_move(&spot, &board, &me);
-        assert_eq!(valid_move, 70);
+        let valid_move = value_of(&spot, &board, &me);
+        assert_eq!(valid_move, 21);
     }
 
     // Board Hazards/Dangers
@@ -822,6 +2296,8 @@ mod value_of_move_tests {
             ..Default::default()
         };
         let board = Board {
+            height: 11,
+            width: 11,
             hazards: vec![
                 Coord { x: 10, y: 0 },
                 Coord { x: 10, y: 1 },
@@ -837,10 +2313,27 @@ mod value_of_move_tests {
             ..Default::default()
         };
         let spot = Coord { x: 10, y: 7 };
-        let value_of_move = value_of_move(&spot, &board, &me);
+        let value_of_move = value_of(&spot, &board, &me);
         assert_eq!(value_of_move, 115);
     }
 
+    #[test]
+    fn hazards_that_would_finish_cornelius_off() {
+        let me = Battlesnake {
+            health: 15,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            hazards: vec![Coord { x: 5, y: 5 }],
+            ..Default::default()
+        };
+        let spot = Coord { x: 5, y: 5 };
+        assert!(value_of(&spot, &board, &me) < 0);
+        assert!(value_of(&spot.up(), &board, &me) > 0);
+    }
+
     #[test]
     fn superstitious_of_zero() {
         let me = Battlesnake::default();
@@ -850,22 +2343,24 @@ mod value_of_move_tests {
             ..Default::default()
         };
         let spot = Coord { x: 0, y: 5 };
-        let value_of_move = value_of_move(&spot, &board, &me);
+        let value_of_move = value_of(&spot, &board, &me);
         assert_eq!(value_of_move, 110);
     }
 
     #[test]
     fn eats_food() {
         let me = Battlesnake {
-            health: 100,
+            health: 75,
             ..Default::default()
         };
         let board = Board {
+            height: 11,
+            width: 11,
             food: vec![Coord { x: 5, y: 5 }],
             ..Default::default()
         };
         let spot = Coord { x: 5, y: 5 };
-        let value_of_move = value_of_move(&spot, &board, &me);
+        let value_of_move = value_of(&spot, &board, &me);
         assert_eq!(value_of_move, 225);
     }
 
@@ -881,9 +2376,10 @@ mod value_of_move_tests {
             food: vec![],
             hazards: vec![],
             snakes: vec![me.clone()],
+            ..Default::default()
         };
         let spot = Coord { x: 5, y: 5 };
-        let valid_move = value_of_move(&spot, &board, &me);
+        let valid_move = value_of(&spot, &board, &me);
         assert_eq!(valid_move, 150);
     }
 }
@@ -5,16 +5,33 @@ extern crate rocket;
 #[macro_use]
 extern crate rocket_contrib;
 
-use log::info;
+use log::{info, warn};
 use rocket::config::{Config, Environment};
 use rocket::http::Status;
+use rocket::State;
 use rocket_contrib::json::{Json, JsonValue};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use std::collections::HashMap;
 use std::env;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
+use logic::Weights;
+use opening::OpeningBook;
+use personality::{Personalities, Personality};
+
+mod bitboard;
+mod duel;
+#[cfg(test)]
+mod fixtures;
+mod grid;
 mod logic;
+mod mcts;
+mod opening;
+mod personality;
+mod rules;
+mod search;
+mod solo;
+mod strategy;
 
 // Request types derived from https://docs.battlesnake.com/references/api#object-definitions
 // For a full example of Game Board data, see https://docs.battlesnake.com/references/api/sample-move-request
@@ -51,14 +68,72 @@ impl Default for Battlesnake {
         }
     }
 }
+impl Battlesnake {
+    pub fn is_teammate(&self, other: &Battlesnake) -> bool {
+        match (&self.squad, &other.squad) {
+            (Some(squad), Some(other_squad)) => {
+                self.id != other.id && !squad.is_empty() && squad == other_squad
+            }
+            _ => false,
+        }
+    }
+}
 
-#[derive(Deserialize, Serialize, Debug, Default)]
+#[derive(Clone, Deserialize, Serialize, Debug, Default)]
 pub struct Board {
     food: Vec<Coord>,
     hazards: Vec<Coord>,
     height: i32,
     snakes: Vec<Battlesnake>,
     width: i32,
+
+    // Copied from the game and the personality, not part of the request
+    #[serde(skip)]
+    ruleset: Ruleset,
+    #[serde(skip)]
+    weights: Weights,
+}
+impl Board {
+    // On wrapped boards, moving off one edge comes back on the opposite edge
+    pub fn wrap(&self, spot: Coord) -> Coord {
+        if !self.ruleset.wrapped() {
+            return spot;
+        }
+        Coord {
+            x: spot.x.rem_euclid(self.width),
+            y: spot.y.rem_euclid(self.height),
+        }
+    }
+
+    pub fn step(&self, spot: &Coord, chosen: Direction) -> Coord {
+        let next = match chosen {
+            Direction::Up => spot.up(),
+            Direction::Down => spot.down(),
+            Direction::Left => spot.left(),
+            Direction::Right => spot.right(),
+        };
+        self.wrap(next)
+    }
+
+    pub fn neighbours(&self, spot: &Coord) -> [Coord; 4] {
+        [
+            self.wrap(spot.up()),
+            self.wrap(spot.down()),
+            self.wrap(spot.left()),
+            self.wrap(spot.right()),
+        ]
+    }
+
+    // Steps between two spots, going round the edges on wrapped boards
+    pub fn distance(&self, from: &Coord, to: &Coord) -> i32 {
+        let x = (from.x - to.x).abs();
+        let y = (from.y - to.y).abs();
+        if self.ruleset.wrapped() {
+            x.min(self.width - x) + y.min(self.height - y)
+        } else {
+            x + y
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
@@ -72,6 +147,12 @@ impl PartialEq for Coord {
     }
 }
 impl Eq for Coord {}
+impl Hash for Coord {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+        self.y.hash(state);
+    }
+}
 impl Coord {
     pub fn down(&self) -> Coord {
         Coord {
@@ -102,13 +183,488 @@ impl Coord {
     }
 }
 
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Direction::Up => "up",
+            Direction::Down => "down",
+            Direction::Left => "left",
+            Direction::Right => "right",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// The game only shows this many characters of a shout
+const MAX_SHOUT_LENGTH: usize = 256;
+
+// Response derived from https://docs.battlesnake.com/references/api#post-move
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+pub struct MoveResponse {
+    #[serde(rename = "move")]
+    chosen: Direction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shout: Option<String>,
+}
+
+impl MoveResponse {
+    pub fn new(chosen: Direction, shout: Option<String>) -> MoveResponse {
+        MoveResponse {
+            chosen,
+            shout: shout.map(|shout| shout.chars().take(MAX_SHOUT_LENGTH).collect()),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn chosen(&self) -> Direction {
+        self.chosen
+    }
+}
+
+#[cfg(test)]
+mod move_response_tests {
+    use super::*;
+
+    #[test]
+    fn writes_move_and_shout() {
+        let response = MoveResponse::new(Direction::Left, Some("Watch out!".to_string()));
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({ "move": "left", "shout": "Watch out!" })
+        );
+    }
+
+    #[test]
+    fn leaves_out_missing_shout() {
+        let response = MoveResponse::new(Direction::Up, None);
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"move":"up"}"#
+        );
+    }
+
+    #[test]
+    fn reads_moves() {
+        let response: MoveResponse =
+            serde_json::from_str(r#"{ "move": "down", "shout": "hiss" }"#).unwrap();
+        assert_eq!(
+            response,
+            MoveResponse::new(Direction::Down, Some("hiss".to_string()))
+        );
+        assert!(serde_json::from_str::<Direction>(r#""sideways""#).is_err());
+    }
+
+    #[test]
+    fn cuts_long_shouts_short() {
+        let response = MoveResponse::new(Direction::Right, Some("s".repeat(300)));
+        assert_eq!(response.shout.unwrap().len(), MAX_SHOUT_LENGTH);
+        let response = MoveResponse::new(Direction::Right, Some("🐍".repeat(300)));
+        assert_eq!(response.shout.unwrap().chars().count(), MAX_SHOUT_LENGTH);
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Default)]
 pub struct Game {
     id: String,
-    ruleset: HashMap<String, Value>,
+    #[serde(default)]
+    map: GameMap,
+    ruleset: Ruleset,
+    #[serde(default)]
+    source: GameSource,
     timeout: u32,
 }
 
+#[cfg(test)]
+mod game_tests {
+    use super::*;
+
+    #[test]
+    fn reads_map_and_source() {
+        let game: Game = serde_json::from_str(
+            r#"{
+                "id": "game",
+                "map": "hz_islands_bridges",
+                "ruleset": { "name": "royale" },
+                "source": "league",
+                "timeout": 500
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(game.map, GameMap::HzIslandsBridges);
+        assert_eq!(game.source, GameSource::League);
+    }
+
+    #[test]
+    fn keeps_unknown_map_and_source() {
+        let game: Game = serde_json::from_str(
+            r#"{
+                "id": "game",
+                "map": "snail_trail",
+                "ruleset": { "name": "standard" },
+                "source": "ladder",
+                "timeout": 500
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(game.map, GameMap::Unknown("snail_trail".to_string()));
+        assert_eq!(game.source, GameSource::Unknown("ladder".to_string()));
+        assert_eq!(game.map.to_string(), "snail_trail");
+    }
+
+    #[test]
+    fn missing_map_and_source() {
+        let game: Game = serde_json::from_str(
+            r#"{
+                "id": "game",
+                "ruleset": { "name": "standard" },
+                "timeout": 500
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(game.map, GameMap::Standard);
+        assert_eq!(game.source, GameSource::Custom);
+    }
+}
+
+// Maps listed at https://docs.battlesnake.com/maps
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+#[serde(from = "String", into = "String")]
+pub enum GameMap {
+    Standard,
+    Empty,
+    Royale,
+    ArcadeMaze,
+    SoloMaze,
+    HzInnerWall,
+    HzRings,
+    HzColumns,
+    HzRiversBridges,
+    HzIslandsBridges,
+    HzSpiral,
+    HzScatter,
+    Unknown(String),
+}
+
+impl Default for GameMap {
+    fn default() -> GameMap {
+        GameMap::Standard
+    }
+}
+
+impl From<String> for GameMap {
+    fn from(map: String) -> GameMap {
+        match map.as_str() {
+            "standard" => GameMap::Standard,
+            "empty" => GameMap::Empty,
+            "royale" => GameMap::Royale,
+            "arcade_maze" => GameMap::ArcadeMaze,
+            "solo_maze" => GameMap::SoloMaze,
+            "hz_inner_wall" => GameMap::HzInnerWall,
+            "hz_rings" => GameMap::HzRings,
+            "hz_columns" => GameMap::HzColumns,
+            "hz_rivers_bridges" => GameMap::HzRiversBridges,
+            "hz_islands_bridges" => GameMap::HzIslandsBridges,
+            "hz_spiral" => GameMap::HzSpiral,
+            "hz_scatter" => GameMap::HzScatter,
+            _ => GameMap::Unknown(map),
+        }
+    }
+}
+
+impl From<GameMap> for String {
+    fn from(map: GameMap) -> String {
+        match map {
+            GameMap::Standard => "standard".to_string(),
+            GameMap::Empty => "empty".to_string(),
+            GameMap::Royale => "royale".to_string(),
+            GameMap::ArcadeMaze => "arcade_maze".to_string(),
+            GameMap::SoloMaze => "solo_maze".to_string(),
+            GameMap::HzInnerWall => "hz_inner_wall".to_string(),
+            GameMap::HzRings => "hz_rings".to_string(),
+            GameMap::HzColumns => "hz_columns".to_string(),
+            GameMap::HzRiversBridges => "hz_rivers_bridges".to_string(),
+            GameMap::HzIslandsBridges => "hz_islands_bridges".to_string(),
+            GameMap::HzSpiral => "hz_spiral".to_string(),
+            GameMap::HzScatter => "hz_scatter".to_string(),
+            GameMap::Unknown(map) => map,
+        }
+    }
+}
+
+impl fmt::Display for GameMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", String::from(self.clone()))
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+#[serde(from = "String", into = "String")]
+pub enum GameSource {
+    Tournament,
+    League,
+    Arena,
+    Challenge,
+    Custom,
+    Unknown(String),
+}
+
+impl Default for GameSource {
+    fn default() -> GameSource {
+        GameSource::Custom
+    }
+}
+
+impl From<String> for GameSource {
+    fn from(source: String) -> GameSource {
+        match source.as_str() {
+            "tournament" => GameSource::Tournament,
+            "league" => GameSource::League,
+            "arena" => GameSource::Arena,
+            "challenge" => GameSource::Challenge,
+            "custom" => GameSource::Custom,
+            _ => GameSource::Unknown(source),
+        }
+    }
+}
+
+impl From<GameSource> for String {
+    fn from(source: GameSource) -> String {
+        match source {
+            GameSource::Tournament => "tournament".to_string(),
+            GameSource::League => "league".to_string(),
+            GameSource::Arena => "arena".to_string(),
+            GameSource::Challenge => "challenge".to_string(),
+            GameSource::Custom => "custom".to_string(),
+            GameSource::Unknown(source) => source,
+        }
+    }
+}
+
+impl fmt::Display for GameSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", String::from(self.clone()))
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, Default)]
+#[serde(default)]
+pub struct Ruleset {
+    name: RulesetName,
+    version: String,
+    settings: RulesetSettings,
+}
+impl Ruleset {
+    pub fn wrapped(&self) -> bool {
+        matches!(
+            self.name,
+            RulesetName::Wrapped | RulesetName::WrappedConstrictor
+        )
+    }
+
+    pub fn constrictor(&self) -> bool {
+        matches!(
+            self.name,
+            RulesetName::Constrictor | RulesetName::WrappedConstrictor
+        )
+    }
+}
+
+#[cfg(test)]
+mod ruleset_tests {
+    use super::*;
+
+    #[test]
+    fn reads_ruleset_settings() {
+        let game: Game = serde_json::from_str(
+            r#"{
+                "id": "game",
+                "timeout": 500,
+                "ruleset": {
+                    "name": "royale",
+                    "version": "v1.0.22",
+                    "settings": {
+                        "foodSpawnChance": 25,
+                        "minimumFood": 2,
+                        "hazardDamagePerTurn": 30,
+                        "royale": { "shrinkEveryNTurns": 10 },
+                        "squad": {
+                            "allowBodyCollisions": true,
+                            "sharedElimination": true,
+                            "sharedHealth": false,
+                            "sharedLength": false
+                        }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(game.ruleset.name, RulesetName::Royale);
+        let settings = game.ruleset.settings;
+        assert_eq!(settings.food_spawn_chance, 25);
+        assert_eq!(settings.minimum_food, 2);
+        assert_eq!(settings.hazard_damage_per_turn, 30);
+        assert_eq!(settings.royale.shrink_every_n_turns, 10);
+        assert!(settings.squad.allow_body_collisions);
+        assert!(settings.squad.shared_elimination);
+    }
+
+    #[test]
+    fn missing_settings_fall_back() {
+        let game: Game = serde_json::from_str(
+            r#"{
+                "id": "game",
+                "timeout": 500,
+                "ruleset": {
+                    "name": "standard",
+                    "settings": { "foodSpawnChance": 25 }
+                }
+            }"#,
+        )
+        .unwrap();
+        let settings = game.ruleset.settings;
+        assert_eq!(settings.food_spawn_chance, 25);
+        assert_eq!(settings.hazard_damage_per_turn, 14);
+        assert_eq!(settings.royale.shrink_every_n_turns, 25);
+        assert!(!settings.squad.allow_body_collisions);
+    }
+
+    #[test]
+    fn keeps_unknown_names() {
+        let game: Game = serde_json::from_str(
+            r#"{
+                "id": "game",
+                "timeout": 500,
+                "ruleset": {
+                    "name": "snail-mode",
+                    "version": "cli",
+                    "somethingNew": { "answer": 42 }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            game.ruleset.name,
+            RulesetName::Unknown("snail-mode".to_string())
+        );
+        assert_eq!(game.ruleset.settings.hazard_damage_per_turn, 14);
+    }
+
+    #[test]
+    fn wrapped_constrictor_is_both() {
+        let ruleset = Ruleset {
+            name: RulesetName::from("wrapped-constrictor".to_string()),
+            ..Default::default()
+        };
+        assert!(ruleset.wrapped());
+        assert!(ruleset.constrictor());
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+#[serde(from = "String", into = "String")]
+pub enum RulesetName {
+    Standard,
+    Solo,
+    Royale,
+    Squad,
+    Constrictor,
+    Wrapped,
+    WrappedConstrictor,
+    Unknown(String),
+}
+
+impl Default for RulesetName {
+    fn default() -> RulesetName {
+        RulesetName::Standard
+    }
+}
+
+impl From<String> for RulesetName {
+    fn from(name: String) -> RulesetName {
+        match name.as_str() {
+            "standard" => RulesetName::Standard,
+            "solo" => RulesetName::Solo,
+            "royale" => RulesetName::Royale,
+            "squad" => RulesetName::Squad,
+            "constrictor" => RulesetName::Constrictor,
+            "wrapped" => RulesetName::Wrapped,
+            "wrapped-constrictor" => RulesetName::WrappedConstrictor,
+            _ => RulesetName::Unknown(name),
+        }
+    }
+}
+
+impl From<RulesetName> for String {
+    fn from(name: RulesetName) -> String {
+        match name {
+            RulesetName::Standard => "standard".to_string(),
+            RulesetName::Solo => "solo".to_string(),
+            RulesetName::Royale => "royale".to_string(),
+            RulesetName::Squad => "squad".to_string(),
+            RulesetName::Constrictor => "constrictor".to_string(),
+            RulesetName::Wrapped => "wrapped".to_string(),
+            RulesetName::WrappedConstrictor => "wrapped-constrictor".to_string(),
+            RulesetName::Unknown(name) => name,
+        }
+    }
+}
+
+// Settings derived from https://docs.battlesnake.com/references/api#ruleset-settings
+// Anything the game leaves out falls back to the standard values.
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RulesetSettings {
+    food_spawn_chance: i32,
+    minimum_food: i32,
+    hazard_damage_per_turn: i32,
+    royale: RoyaleSettings,
+    squad: SquadSettings,
+}
+
+impl Default for RulesetSettings {
+    fn default() -> RulesetSettings {
+        Self {
+            food_spawn_chance: 15,
+            minimum_food: 1,
+            hazard_damage_per_turn: 14,
+            royale: RoyaleSettings::default(),
+            squad: SquadSettings::default(),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RoyaleSettings {
+    shrink_every_n_turns: i32,
+}
+
+impl Default for RoyaleSettings {
+    fn default() -> RoyaleSettings {
+        Self {
+            shrink_every_n_turns: 25,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SquadSettings {
+    allow_body_collisions: bool,
+    shared_elimination: bool,
+    shared_health: bool,
+    shared_length: bool,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct GameState {
     board: Board,
@@ -116,15 +672,88 @@ pub struct GameState {
     turn: u32,
     you: Battlesnake,
 }
+impl GameState {
+    // Copies the ruleset and weights onto the board so moves can be judged without the game
+    fn prepare(mut self, weights: Weights) -> GameState {
+        self.board.ruleset = self.game.ruleset.clone();
+        self.board.weights = weights;
+        self
+    }
+}
 
 #[get("/")]
-fn handle_index() -> JsonValue {
-    logic::get_info()
+fn handle_index(personalities: State<Personalities>) -> JsonValue {
+    info(personalities.default())
 }
 
 #[post("/start", format = "json", data = "<start_req>")]
-fn handle_start(start_req: Json<GameState>) -> Status {
-    logic::start(
+fn handle_start(personalities: State<Personalities>, start_req: Json<GameState>) -> Status {
+    start(personalities.default(), start_req)
+}
+
+#[post("/move", format = "json", data = "<move_req>")]
+fn handle_move(
+    personalities: State<Personalities>,
+    book: State<OpeningBook>,
+    move_req: Json<GameState>,
+) -> Json<MoveResponse> {
+    choose_move(personalities.default(), &book, move_req)
+}
+
+#[post("/end", format = "json", data = "<end_req>")]
+fn handle_end(personalities: State<Personalities>, end_req: Json<GameState>) -> Status {
+    end(personalities.default(), end_req)
+}
+
+// The same routes again for each personality, e.g. /mcts/move
+#[get("/<snake>")]
+fn handle_snake_index(personalities: State<Personalities>, snake: String) -> Option<JsonValue> {
+    personalities.get(&snake).map(info)
+}
+
+#[post("/<snake>/start", format = "json", data = "<start_req>")]
+fn handle_snake_start(
+    personalities: State<Personalities>,
+    snake: String,
+    start_req: Json<GameState>,
+) -> Status {
+    match personalities.get(&snake) {
+        Some(personality) => start(personality, start_req),
+        None => Status::NotFound,
+    }
+}
+
+#[post("/<snake>/move", format = "json", data = "<move_req>")]
+fn handle_snake_move(
+    personalities: State<Personalities>,
+    book: State<OpeningBook>,
+    snake: String,
+    move_req: Json<GameState>,
+) -> Option<Json<MoveResponse>> {
+    personalities
+        .get(&snake)
+        .map(|personality| choose_move(personality, &book, move_req))
+}
+
+#[post("/<snake>/end", format = "json", data = "<end_req>")]
+fn handle_snake_end(
+    personalities: State<Personalities>,
+    snake: String,
+    end_req: Json<GameState>,
+) -> Status {
+    match personalities.get(&snake) {
+        Some(personality) => end(personality, end_req),
+        None => Status::NotFound,
+    }
+}
+
+fn info(personality: &Personality) -> JsonValue {
+    personality.strategy.info(&personality.appearance)
+}
+
+fn start(personality: &Personality, start_req: Json<GameState>) -> Status {
+    let start_req = start_req.into_inner().prepare(personality.weights);
+    personality.strategy.start(
         &start_req.game,
         &start_req.turn,
         &start_req.board,
@@ -134,21 +763,44 @@ fn handle_start(start_req: Json<GameState>) -> Status {
     Status::Ok
 }
 
-#[post("/move", format = "json", data = "<move_req>")]
-fn handle_move(move_req: Json<GameState>) -> JsonValue {
-    let chosen = logic::get_move(
-        &move_req.game,
-        &move_req.turn,
-        &move_req.board,
-        &move_req.you,
+// Every personality plays the same openings, then it's up to their strategy
+fn choose_move(
+    personality: &Personality,
+    book: &OpeningBook,
+    move_req: Json<GameState>,
+) -> Json<MoveResponse> {
+    let move_req = move_req.into_inner().prepare(personality.weights);
+    info!(
+        "{} HUNGER {}",
+        move_req.game.id,
+        logic::hunger(&move_req.board, &move_req.you)
     );
+    let opening = if personality.opening_book {
+        book.best_move(&move_req.board, &move_req.you, move_req.turn)
+    } else {
+        None
+    };
+    let response = match opening {
+        Some(chosen) => {
+            info!("{} MOVE {} (opening book)", move_req.game.id, chosen);
+            MoveResponse::new(chosen, None)
+        }
+        None => personality.strategy.get_move(
+            &move_req.game,
+            &move_req.turn,
+            &move_req.board,
+            &move_req.you,
+        ),
+    };
 
-    return json!({ "move": chosen });
+    Json(response)
 }
 
-#[post("/end", format = "json", data = "<end_req>")]
-fn handle_end(end_req: Json<GameState>) -> Status {
-    logic::end(&end_req.game, &end_req.turn, &end_req.board, &end_req.you);
+fn end(personality: &Personality, end_req: Json<GameState>) -> Status {
+    let end_req = end_req.into_inner().prepare(personality.weights);
+    personality
+        .strategy
+        .end(&end_req.game, &end_req.turn, &end_req.board, &end_req.you);
 
     Status::Ok
 }
@@ -158,9 +810,27 @@ fn main() {
     let env_port = env::var("PORT").ok();
     let env_port = env_port.as_deref().unwrap_or("8080");
     let port = env_port.parse::<u16>().unwrap();
+    let env_strategy = env::var("STRATEGY").ok();
+    let env_strategy = env_strategy
+        .as_deref()
+        .unwrap_or(strategy::DEFAULT_STRATEGY);
+    let chosen_strategy = strategy::from_name(env_strategy).unwrap_or_else(|| {
+        panic!(
+            "Unknown STRATEGY {}, expected one of {}",
+            env_strategy,
+            strategy::STRATEGIES.join(", ")
+        )
+    });
 
     env_logger::init();
 
+    let env_book = env::var("OPENING_BOOK").ok();
+    let env_book = env_book.as_deref().unwrap_or(opening::DEFAULT_BOOK);
+    let book = OpeningBook::load(env_book).unwrap_or_else(|err| {
+        warn!("Playing without an opening book, {}", err);
+        OpeningBook::default()
+    });
+
     let config = Config::build(Environment::Development)
         .address(address)
         .port(port)
@@ -168,13 +838,31 @@ fn main() {
         .unwrap();
 
     info!(
-        "Starting Battlesnake Server at http://{}:{}...",
-        address, port
+        "Starting Battlesnake Server at http://{}:{} with the {} strategy...",
+        address,
+        port,
+        chosen_strategy.name()
+    );
+    let personalities = Personalities::new(chosen_strategy);
+    info!(
+        "Also serving {} under their own paths",
+        personalities.names().join(", ")
     );
     rocket::custom(config)
+        .manage(personalities)
+        .manage(book)
         .mount(
             "/",
-            routes![handle_index, handle_start, handle_move, handle_end],
+            routes![
+                handle_index,
+                handle_start,
+                handle_move,
+                handle_end,
+                handle_snake_index,
+                handle_snake_start,
+                handle_snake_move,
+                handle_snake_end,
+            ],
         )
         .launch();
 }
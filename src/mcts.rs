@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use log::info;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::bitboard::Bitboard;
+use crate::grid::Grid;
+use crate::logic::{self, MOVES};
+use crate::{rules, search};
+use crate::{Battlesnake, Board, Direction, Game};
+
+const EXPLORATION: f64 = 1.4;
+const PLAYOUT_DEPTH: u32 = 12;
+const MAX_ITERATIONS: u32 = 100_000;
+
+#[derive(Clone, Copy, Default)]
+struct Stat {
+    visits: u32,
+    reward: f64,
+}
+
+// Every snake keeps their own statistics for their own moves (decoupled UCT),
+// and children are keyed by the moves everyone made together.
+struct Node {
+    board: Board,
+    visits: u32,
+    stats: Vec<[Stat; 4]>,
+    children: HashMap<Vec<usize>, Node>,
+}
+
+impl Node {
+    fn new(board: Board) -> Node {
+        Self {
+            stats: vec![[Stat::default(); 4]; board.snakes.len()],
+            board,
+            visits: 0,
+            children: HashMap::new(),
+        }
+    }
+}
+
+pub fn get_move(game: &Game, _turn: &u32, board: &Board, me: &Battlesnake) -> Direction {
+    let deadline = search::deadline(game, Instant::now());
+    let mut rng = StdRng::from_entropy();
+    let chosen = best_move(board, me, &mut rng, Some(deadline), MAX_ITERATIONS);
+
+    info!("{} MOVE {} (mcts)", game.id, chosen);
+
+    chosen
+}
+
+fn best_move<R: Rng>(
+    board: &Board,
+    me: &Battlesnake,
+    rng: &mut R,
+    deadline: Option<Instant>,
+    max_iterations: u32,
+) -> Direction {
+    let mut root = Node::new(board.clone());
+    for _ in 0..max_iterations {
+        if let Some(deadline) = deadline {
+            if Instant::now() >= deadline {
+                break;
+            }
+        }
+        iterate(&mut root, &me.id, rng);
+    }
+
+    let me_index = board.snakes.iter().position(|snake| snake.id == me.id);
+    match me_index {
+        Some(me_index) if !root.children.is_empty() => {
+            let stats = &root.stats[me_index];
+            let best = (0..MOVES.len())
+                .max_by_key(|index| stats[*index].visits)
+                .unwrap();
+            MOVES[best]
+        }
+        _ => logic::greedy_move(board, me),
+    }
+}
+
+fn iterate<R: Rng>(node: &mut Node, my_id: &str, rng: &mut R) -> HashMap<String, f64> {
+    if !alive(&node.board, my_id) {
+        node.visits += 1;
+        return rewards(&node.board);
+    }
+    if node.visits == 0 {
+        node.visits += 1;
+        return playout(&node.board, my_id, rng);
+    }
+
+    let joint: Vec<usize> = node
+        .stats
+        .iter()
+        .map(|stats| select(stats, node.visits))
+        .collect();
+    let board = &node.board;
+    let child = node.children.entry(joint.clone()).or_insert_with(|| {
+        let moves = board
+            .snakes
+            .iter()
+            .zip(joint.iter())
+            .map(|(snake, index)| (snake.id.clone(), MOVES[*index]))
+            .collect();
+        Node::new(rules::advance(board, &moves))
+    });
+    let rewards = iterate(child, my_id, rng);
+
+    for ((snake, stats), index) in node
+        .board
+        .snakes
+        .iter()
+        .zip(node.stats.iter_mut())
+        .zip(joint)
+    {
+        stats[index].visits += 1;
+        stats[index].reward += rewards.get(&snake.id).copied().unwrap_or(0.0);
+    }
+    node.visits += 1;
+
+    rewards
+}
+
+// UCB1, trying every move at least once before exploiting any of them
+fn select(stats: &[Stat; 4], parent_visits: u32) -> usize {
+    let mut best = 0;
+    let mut best_score = f64::MIN;
+    for (index, stat) in stats.iter().enumerate() {
+        if stat.visits == 0 {
+            return index;
+        }
+        let mean = stat.reward / stat.visits as f64;
+        let score = mean + EXPLORATION * ((parent_visits as f64).ln() / stat.visits as f64).sqrt();
+        if score > best_score {
+            best = index;
+            best_score = score;
+        }
+    }
+    best
+}
+
+// Plays random moves that don't crash straight into a wall or a body
+fn playout<R: Rng>(board: &Board, my_id: &str, rng: &mut R) -> HashMap<String, f64> {
+    let mut bitboard = match Bitboard::from_board(board) {
+        Some(bitboard) => bitboard,
+        None => return playout_board(board, my_id, rng),
+    };
+    let me = match board.snakes.iter().position(|snake| snake.id == my_id) {
+        Some(me) => me,
+        None => return rewards(board),
+    };
+    for _ in 0..PLAYOUT_DEPTH {
+        if !bitboard.survivors().any(|index| index == me) {
+            break;
+        }
+        let moves: Vec<Direction> = (0..bitboard.snake_count())
+            .map(|snake| {
+                let options = bitboard.legal_moves(snake);
+                options.choose(rng).copied().unwrap_or(Direction::Up)
+            })
+            .collect();
+        bitboard.advance(&moves);
+    }
+
+    let alive = bitboard.snake_count() as f64;
+    bitboard
+        .survivors()
+        .map(|index| (board.snakes[index].id.clone(), 0.5 + 0.5 / alive))
+        .collect()
+}
+
+// The same playout on the full board, for games the bitboard can't play
+fn playout_board<R: Rng>(board: &Board, my_id: &str, rng: &mut R) -> HashMap<String, f64> {
+    let mut board = board.clone();
+    for _ in 0..PLAYOUT_DEPTH {
+        if !alive(&board, my_id) {
+            break;
+        }
+        let grid = Grid::new(&board);
+        let moves = board
+            .snakes
+            .iter()
+            .map(|snake| {
+                let options: Vec<Direction> = MOVES
+                    .iter()
+                    .copied()
+                    .filter(|candidate| {
+                        logic::valid_move(&board.step(&snake.head, *candidate), &grid)
+                    })
+                    .collect();
+                let chosen = options.choose(rng).copied().unwrap_or(Direction::Up);
+                (snake.id.clone(), chosen)
+            })
+            .collect();
+        board = rules::advance(&board, &moves);
+    }
+    rewards(&board)
+}
+
+fn alive(board: &Board, my_id: &str) -> bool {
+    board.snakes.iter().any(|snake| snake.id == my_id)
+}
+
+// Surviving is what matters, and sharing the board with fewer snakes is better
+fn rewards(board: &Board) -> HashMap<String, f64> {
+    let alive = board.snakes.len() as f64;
+    board
+        .snakes
+        .iter()
+        .map(|snake| (snake.id.clone(), 0.5 + 0.5 / alive))
+        .collect()
+}
+
+#[cfg(test)]
+mod best_move_tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn skips_food_at_the_end_of_a_dead_end() {
+        let (board, me) = fixtures::dead_end();
+        let mut rng = StdRng::seed_from_u64(7);
+        let chosen = best_move(&board, &me, &mut rng, None, 3000);
+        assert!(chosen == Direction::Up || chosen == Direction::Down);
+    }
+
+    #[test]
+    fn falls_back_to_greedy_without_time() {
+        let (board, me) = fixtures::facing_the_wall();
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(
+            best_move(&board, &me, &mut rng, Some(Instant::now()), 3000),
+            Direction::Down
+        );
+    }
+}
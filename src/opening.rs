@@ -0,0 +1,244 @@
+use std::fs;
+
+use serde::Deserialize;
+
+use crate::grid::Grid;
+use crate::logic;
+use crate::{Battlesnake, Board, Coord, Direction, RulesetName};
+
+// Where the book is read from, unless the OPENING_BOOK environment variable says otherwise
+pub const DEFAULT_BOOK: &str = "openings.json";
+
+// One known move for the start of a standard game. Every position is looked at as if
+// the centre of the board were up and to the right of Cornelius, so one entry covers
+// all four corners.
+#[derive(Debug, Deserialize, PartialEq)]
+struct Opening {
+    width: i32,
+    height: i32,
+    turn: u32,
+    // Only played when there's food this far from Cornelius' head, or always if left out
+    #[serde(default)]
+    food: Option<Coord>,
+    // Where the other snakes' heads are from Cornelius' head, or anywhere if left out
+    #[serde(default)]
+    snakes: Option<Vec<Coord>>,
+    #[serde(rename = "move")]
+    chosen: Direction,
+}
+
+// Openings are tried in the order they're written, so the better move goes first
+#[derive(Debug, Default, PartialEq)]
+pub struct OpeningBook {
+    openings: Vec<Opening>,
+}
+
+impl OpeningBook {
+    pub fn from_json(json: &str) -> Result<OpeningBook, String> {
+        let openings: Vec<Opening> = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        Ok(OpeningBook { openings })
+    }
+
+    // Read once when the server starts, so changing the book only needs a restart
+    pub fn load(path: &str) -> Result<OpeningBook, String> {
+        let json = fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+        OpeningBook::from_json(&json).map_err(|err| format!("{}: {}", path, err))
+    }
+
+    // The first opening that fits the board and leads somewhere safe.
+    // Only standard games start from known positions.
+    pub fn best_move(&self, board: &Board, me: &Battlesnake, turn: u32) -> Option<Direction> {
+        if board.ruleset.name != RulesetName::Standard || board.snakes.len() < 2 {
+            return None;
+        }
+        let frame = Frame::new(board, me);
+        let grid = Grid::for_snake(board, me);
+        self.openings
+            .iter()
+            .filter(|opening| {
+                opening.width == board.width
+                    && opening.height == board.height
+                    && opening.turn == turn
+            })
+            .filter(|opening| match &opening.food {
+                Some(offset) => grid.has_food(&frame.spot(offset)),
+                None => true,
+            })
+            .filter(|opening| match &opening.snakes {
+                Some(offsets) => frame.has_layout(board, me, offsets, turn),
+                None => true,
+            })
+            .map(|opening| frame.chosen(opening.chosen))
+            .find(|chosen| {
+                let spot = board.step(&me.head, *chosen);
+                logic::valid_move(&spot, &grid) && !grid.might_have_snake(&spot)
+            })
+    }
+}
+
+// Flips the board around Cornelius so that the centre is up and to the right of them
+struct Frame {
+    head: Coord,
+    flip_x: bool,
+    flip_y: bool,
+}
+
+impl Frame {
+    fn new(board: &Board, me: &Battlesnake) -> Frame {
+        Frame {
+            head: me.head,
+            flip_x: me.head.x * 2 > board.width - 1,
+            flip_y: me.head.y * 2 > board.height - 1,
+        }
+    }
+
+    // The spot an offset from Cornelius' head in the book points to on the real board
+    fn spot(&self, offset: &Coord) -> Coord {
+        let x = if self.flip_x { -offset.x } else { offset.x };
+        let y = if self.flip_y { -offset.y } else { offset.y };
+        Coord {
+            x: self.head.x + x,
+            y: self.head.y + y,
+        }
+    }
+
+    // Whether the other snakes sit where the book says. Everyone has been moving since the
+    // start, so each head can have drifted two cells a turn from its spot.
+    fn has_layout(&self, board: &Board, me: &Battlesnake, offsets: &[Coord], turn: u32) -> bool {
+        let heads: Vec<Coord> = board
+            .snakes
+            .iter()
+            .filter(|snake| snake.id != me.id)
+            .map(|snake| snake.head)
+            .collect();
+        let drift = 2 * turn as i32;
+        heads.len() == offsets.len()
+            && offsets.iter().all(|offset| {
+                let spot = self.spot(offset);
+                heads
+                    .iter()
+                    .any(|head| board.distance(head, &spot) <= drift)
+            })
+    }
+
+    // The move in the book turned around to match the real board
+    fn chosen(&self, chosen: Direction) -> Direction {
+        match chosen {
+            Direction::Up if self.flip_y => Direction::Down,
+            Direction::Down if self.flip_y => Direction::Up,
+            Direction::Left if self.flip_x => Direction::Right,
+            Direction::Right if self.flip_x => Direction::Left,
+            _ => chosen,
+        }
+    }
+}
+
+#[cfg(test)]
+mod opening_book_tests {
+    use super::*;
+
+    fn start(id: &str, head: Coord) -> Battlesnake {
+        Battlesnake {
+            id: id.to_string(),
+            head,
+            body: vec![head, head, head],
+            length: 3,
+            ..Default::default()
+        }
+    }
+
+    fn shipped() -> OpeningBook {
+        OpeningBook::from_json(include_str!("../openings.json")).unwrap()
+    }
+
+    #[test]
+    fn grabs_the_starting_food_from_every_corner() {
+        let snakes = vec![
+            start("me", Coord { x: 1, y: 1 }),
+            start("hettie", Coord { x: 9, y: 9 }),
+            start("maud", Coord { x: 1, y: 9 }),
+            start("ada", Coord { x: 9, y: 1 }),
+        ];
+        let food = vec![
+            Coord { x: 2, y: 2 },
+            Coord { x: 8, y: 8 },
+            Coord { x: 2, y: 8 },
+            Coord { x: 8, y: 2 },
+        ];
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: food.clone(),
+            snakes: snakes.clone(),
+            ..Default::default()
+        };
+        for (snake, food) in snakes.iter().zip(food.iter()) {
+            let chosen = shipped().best_move(&board, snake, 0).unwrap();
+            assert_eq!(board.distance(&board.step(&snake.head, chosen), food), 1);
+        }
+    }
+
+    #[test]
+    fn heads_for_the_centre_once_fed() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 8, y: 9 },
+            body: vec![
+                Coord { x: 8, y: 9 },
+                Coord { x: 7, y: 9 },
+                Coord { x: 7, y: 9 },
+                Coord { x: 7, y: 9 },
+            ],
+            length: 4,
+            ..Default::default()
+        };
+        let mut board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![
+                me.clone(),
+                start("hettie", Coord { x: 1, y: 1 }),
+                start("maud", Coord { x: 1, y: 9 }),
+                start("ada", Coord { x: 9, y: 1 }),
+            ],
+            ..Default::default()
+        };
+        // Going left towards the centre would bite their own neck
+        assert_eq!(shipped().best_move(&board, &me, 2), Some(Direction::Down));
+        // Maud has come over to meet Cornelius, which the book knows nothing about
+        board.snakes[2] = start("maud", Coord { x: 5, y: 9 });
+        assert_eq!(shipped().best_move(&board, &me, 2), None);
+        board.snakes.truncate(2);
+        assert_eq!(shipped().best_move(&board, &me, 2), None);
+    }
+
+    #[test]
+    fn only_plays_standard_games() {
+        let me = start("me", Coord { x: 1, y: 1 });
+        let mut board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 2, y: 2 }],
+            snakes: vec![
+                me.clone(),
+                start("hettie", Coord { x: 9, y: 9 }),
+                start("maud", Coord { x: 1, y: 9 }),
+                start("ada", Coord { x: 9, y: 1 }),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(shipped().best_move(&board, &me, 0), Some(Direction::Up));
+        board.ruleset.name = RulesetName::Wrapped;
+        assert_eq!(shipped().best_move(&board, &me, 0), None);
+        board.ruleset.name = RulesetName::Standard;
+        board.width = 13;
+        board.height = 13;
+        assert_eq!(shipped().best_move(&board, &me, 0), None);
+    }
+
+    #[test]
+    fn rejects_unknown_moves() {
+        let json = r#"[{ "width": 11, "height": 11, "turn": 0, "move": "sideways" }]"#;
+        assert!(OpeningBook::from_json(json).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use crate::logic::Weights;
+use crate::strategy::{self, Strategy};
+
+// Customizations shown on the board, see https://docs.battlesnake.com/references/personalization
+#[derive(Clone, Copy)]
+pub struct Appearance {
+    pub color: &'static str,
+    pub head: &'static str,
+    pub tail: &'static str,
+}
+
+pub const CORNELIUS: Appearance = Appearance {
+    color: "#c88b4c",
+    head: "bendr",
+    tail: "round-bum",
+};
+
+// A variant of Cornelius served under its own path, e.g. /mcts/move
+pub struct Personality {
+    pub appearance: Appearance,
+    pub strategy: Box<dyn Strategy>,
+    pub weights: Weights,
+    // Whether the first turns of standard games come from the opening book
+    pub opening_book: bool,
+}
+
+struct Variant {
+    // Used as the path prefix, so it can't be "start", "move" or "end"
+    name: &'static str,
+    strategy: &'static str,
+    appearance: Appearance,
+    weights: Weights,
+    // Left off for variants that are there to compare strategies, so the book can't skew them
+    opening_book: bool,
+}
+
+const VARIANTS: [Variant; 4] = [
+    Variant {
+        name: "search",
+        strategy: "search",
+        appearance: CORNELIUS,
+        weights: Weights::CAUTIOUS,
+        opening_book: true,
+    },
+    Variant {
+        name: "mcts",
+        strategy: "mcts",
+        appearance: Appearance {
+            color: "#4c89c8",
+            head: "smart-caterpillar",
+            tail: "curled",
+        },
+        weights: Weights::CAUTIOUS,
+        opening_book: false,
+    },
+    Variant {
+        name: "greedy",
+        strategy: "greedy",
+        appearance: Appearance {
+            color: "#6bc84c",
+            head: "tongue",
+            tail: "hook",
+        },
+        weights: Weights::CAUTIOUS,
+        opening_book: false,
+    },
+    // Goes looking for head-to-heads with smaller snakes
+    Variant {
+        name: "hunter",
+        strategy: "search",
+        appearance: Appearance {
+            color: "#c84c4c",
+            head: "fang",
+            tail: "sharp",
+        },
+        weights: Weights { aggression: 60 },
+        opening_book: true,
+    },
+];
+
+pub struct Personalities {
+    default: Personality,
+    named: HashMap<&'static str, Personality>,
+}
+
+impl Personalities {
+    // The default snake keeps the routes at / and plays cautiously with the strategy picked at startup
+    pub fn new(default_strategy: Box<dyn Strategy>) -> Personalities {
+        let named = VARIANTS
+            .iter()
+            .map(|variant| {
+                // A typo here would otherwise only show up as a 404 on the variant's routes
+                let strategy = strategy::from_name(variant.strategy).unwrap_or_else(|| {
+                    panic!(
+                        "Variant {} plays unknown strategy {}",
+                        variant.name, variant.strategy
+                    )
+                });
+                (
+                    variant.name,
+                    Personality {
+                        appearance: variant.appearance,
+                        strategy,
+                        weights: variant.weights,
+                        opening_book: variant.opening_book,
+                    },
+                )
+            })
+            .collect();
+        Personalities {
+            default: Personality {
+                appearance: CORNELIUS,
+                strategy: default_strategy,
+                weights: Weights::CAUTIOUS,
+                opening_book: true,
+            },
+            named,
+        }
+    }
+
+    pub fn default(&self) -> &Personality {
+        &self.default
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Personality> {
+        self.named.get(name)
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = self.named.keys().copied().collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+#[cfg(test)]
+mod personalities_tests {
+    use super::*;
+
+    #[test]
+    fn variants_play_their_own_strategy() {
+        let personalities = Personalities::new(Box::new(strategy::Greedy));
+        let mcts = personalities.get("mcts").unwrap();
+        assert_eq!(mcts.strategy.name(), "mcts");
+        assert_eq!(mcts.appearance.color, "#4c89c8");
+        assert_eq!(personalities.default().strategy.name(), "greedy");
+        assert_eq!(personalities.default().appearance.color, CORNELIUS.color);
+    }
+
+    #[test]
+    fn hunter_is_the_aggressive_one() {
+        let personalities = Personalities::new(Box::new(strategy::Search));
+        assert!(personalities.get("hunter").unwrap().weights.aggression > 0);
+        assert_eq!(personalities.get("search").unwrap().weights.aggression, 0);
+        assert_eq!(personalities.default().weights.aggression, 0);
+    }
+
+    #[test]
+    fn strategy_comparisons_skip_the_opening_book() {
+        let personalities = Personalities::new(Box::new(strategy::Search));
+        assert!(!personalities.get("mcts").unwrap().opening_book);
+        assert!(!personalities.get("greedy").unwrap().opening_book);
+        assert!(personalities.get("search").unwrap().opening_book);
+        assert!(personalities.default().opening_book);
+    }
+
+    #[test]
+    fn unknown_personality() {
+        let personalities = Personalities::new(Box::new(strategy::Search));
+        assert!(personalities.get("hettie").is_none());
+    }
+
+    #[test]
+    fn names_do_not_shadow_routes() {
+        let personalities = Personalities::new(Box::new(strategy::Search));
+        assert_eq!(
+            personalities.names(),
+            vec!["greedy", "hunter", "mcts", "search"]
+        );
+        for route in ["start", "move", "end"].iter() {
+            assert!(personalities.get(route).is_none());
+        }
+    }
+}
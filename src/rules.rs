@@ -0,0 +1,709 @@
+use std::collections::HashMap;
+
+use crate::{Battlesnake, Board, Coord, Direction};
+
+pub const MAX_HEALTH: i32 = 100;
+
+// Returns the board after every snake has made their move, following the standard ruleset.
+// Snakes without a move keep travelling in the direction they were already heading.
+pub fn advance(board: &Board, moves: &HashMap<String, Direction>) -> Board {
+    let mut next = board.clone();
+
+    for snake in next.snakes.iter_mut() {
+        let chosen = match moves.get(&snake.id) {
+            Some(chosen) => *chosen,
+            None => default_move(snake),
+        };
+        let head = board.step(&snake.head, chosen);
+        move_snake(snake, head);
+    }
+
+    for snake in next.snakes.iter_mut() {
+        snake.health -= 1;
+        // Stacked hazards each hurt as much as a single one
+        if !next.food.contains(&snake.head) {
+            let hazards = next
+                .hazards
+                .iter()
+                .filter(|spot| **spot == snake.head)
+                .count();
+            snake.health -= next.ruleset.settings.hazard_damage_per_turn * hazards as i32;
+        }
+    }
+
+    if next.ruleset.constrictor() {
+        grow_snakes(&mut next);
+    } else {
+        feed_snakes(&mut next);
+    }
+    share_with_squads(&mut next);
+    eliminate_snakes(&mut next);
+
+    next
+}
+
+fn default_move(snake: &Battlesnake) -> Direction {
+    match (snake.body.first(), snake.body.get(1)) {
+        (Some(head), Some(neck)) if head.x == neck.x + 1 => Direction::Right,
+        (Some(head), Some(neck)) if head.x + 1 == neck.x => Direction::Left,
+        (Some(head), Some(neck)) if head.y + 1 == neck.y => Direction::Down,
+        _ => Direction::Up,
+    }
+}
+
+fn move_snake(snake: &mut Battlesnake, head: Coord) {
+    snake.body.insert(0, head);
+    snake.body.pop();
+    snake.head = head;
+}
+
+fn feed_snakes(board: &mut Board) {
+    let mut eaten = vec![];
+    for snake in board.snakes.iter_mut() {
+        if board.food.contains(&snake.head) {
+            snake.health = MAX_HEALTH;
+            if let Some(&tail) = snake.body.last() {
+                snake.body.push(tail);
+            }
+            snake.length += 1;
+            eaten.push(snake.head);
+        }
+    }
+    board.food.retain(|food| !eaten.contains(food));
+}
+
+// In constrictor games every snake grows every turn and there's no food to eat
+fn grow_snakes(board: &mut Board) {
+    for snake in board.snakes.iter_mut() {
+        snake.health = MAX_HEALTH;
+        if let Some(&tail) = snake.body.last() {
+            snake.body.push(tail);
+        }
+        snake.length += 1;
+    }
+    board.food.clear();
+}
+
+fn share_with_squads(board: &mut Board) {
+    let squad = &board.ruleset.settings.squad;
+    if !squad.shared_health && !squad.shared_length {
+        return;
+    }
+
+    let before = board.snakes.clone();
+    for (snake, original) in board.snakes.iter_mut().zip(before.iter()) {
+        for teammate in before.iter().filter(|other| original.is_teammate(other)) {
+            if squad.shared_health {
+                snake.health = snake.health.max(teammate.health);
+            }
+            if squad.shared_length {
+                while snake.length < teammate.length {
+                    if let Some(&tail) = snake.body.last() {
+                        snake.body.push(tail);
+                    }
+                    snake.length += 1;
+                }
+            }
+        }
+    }
+}
+
+fn out_of_bounds(spot: &Coord, board: &Board) -> bool {
+    spot.x < 0 || spot.y < 0 || spot.x >= board.width || spot.y >= board.height
+}
+
+fn eliminate_snakes(board: &mut Board) {
+    let survivors: Vec<Battlesnake> = board
+        .snakes
+        .iter()
+        .filter(|snake| snake.health > 0 && !out_of_bounds(&snake.head, board))
+        .cloned()
+        .collect();
+
+    let squad = &board.ruleset.settings.squad;
+    let mut eliminated = vec![];
+    for snake in &survivors {
+        for other in &survivors {
+            if squad.allow_body_collisions && snake.is_teammate(other) {
+                continue;
+            }
+            let hit_body = other.body.iter().skip(1).any(|part| part == &snake.head);
+            let lost_head_to_head =
+                other.id != snake.id && other.head == snake.head && other.length >= snake.length;
+            if hit_body || lost_head_to_head {
+                eliminated.push(snake.id.clone());
+                break;
+            }
+        }
+    }
+
+    if squad.shared_elimination {
+        let fallen: Vec<&Battlesnake> = board
+            .snakes
+            .iter()
+            .filter(|snake| {
+                eliminated.contains(&snake.id) || !survivors.iter().any(|s| s.id == snake.id)
+            })
+            .collect();
+        for snake in &survivors {
+            if fallen.iter().any(|other| snake.is_teammate(other)) {
+                eliminated.push(snake.id.clone());
+            }
+        }
+    }
+
+    board.snakes = survivors
+        .into_iter()
+        .filter(|snake| !eliminated.contains(&snake.id))
+        .collect();
+}
+
+#[cfg(test)]
+mod advance_tests {
+    use super::*;
+    use crate::{Ruleset, RulesetName, RulesetSettings, SquadSettings};
+
+    fn moves(chosen: Vec<(&str, Direction)>) -> HashMap<String, Direction> {
+        chosen
+            .into_iter()
+            .map(|(id, chosen)| (id.to_string(), chosen))
+            .collect()
+    }
+
+    #[test]
+    fn body_follows_head() {
+        let me = Battlesnake {
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 4, y: 5 },
+                Coord { x: 3, y: 5 },
+            ],
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me],
+            ..Default::default()
+        };
+        let next = advance(&board, &moves(vec![("CorneliusCodes", Direction::Up)]));
+        let me = &next.snakes[0];
+        assert_eq!(me.head, Coord { x: 5, y: 6 });
+        assert_eq!(
+            me.body,
+            vec![
+                Coord { x: 5, y: 6 },
+                Coord { x: 5, y: 5 },
+                Coord { x: 4, y: 5 },
+            ]
+        );
+        assert_eq!(me.health, 99);
+    }
+
+    #[test]
+    fn keeps_heading_without_a_move() {
+        let me = Battlesnake {
+            head: Coord { x: 5, y: 5 },
+            body: vec![Coord { x: 5, y: 5 }, Coord { x: 4, y: 5 }],
+            length: 2,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me],
+            ..Default::default()
+        };
+        let next = advance(&board, &HashMap::new());
+        assert_eq!(next.snakes[0].head, Coord { x: 6, y: 5 });
+    }
+
+    #[test]
+    fn eating_food_grows_and_heals() {
+        let me = Battlesnake {
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 4, y: 5 },
+                Coord { x: 3, y: 5 },
+            ],
+            health: 40,
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 6, y: 5 }, Coord { x: 0, y: 0 }],
+            snakes: vec![me],
+            ..Default::default()
+        };
+        let next = advance(&board, &moves(vec![("CorneliusCodes", Direction::Right)]));
+        let me = &next.snakes[0];
+        assert_eq!(me.health, 100);
+        assert_eq!(me.length, 4);
+        assert_eq!(
+            me.body,
+            vec![
+                Coord { x: 6, y: 5 },
+                Coord { x: 5, y: 5 },
+                Coord { x: 4, y: 5 },
+                Coord { x: 4, y: 5 },
+            ]
+        );
+        assert_eq!(next.food, vec![Coord { x: 0, y: 0 }]);
+    }
+
+    #[test]
+    fn hazards_hurt() {
+        let me = Battlesnake {
+            head: Coord { x: 5, y: 5 },
+            body: vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }],
+            health: 75,
+            length: 2,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            hazards: vec![Coord { x: 5, y: 6 }],
+            snakes: vec![me],
+            ..Default::default()
+        };
+        let next = advance(&board, &moves(vec![("CorneliusCodes", Direction::Up)]));
+        assert_eq!(next.snakes[0].health, 60);
+    }
+
+    #[test]
+    fn hazards_hurt_as_much_as_the_ruleset_says() {
+        let me = Battlesnake {
+            head: Coord { x: 5, y: 5 },
+            body: vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }],
+            health: 75,
+            length: 2,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            hazards: vec![Coord { x: 5, y: 6 }],
+            snakes: vec![me],
+            ruleset: Ruleset {
+                settings: RulesetSettings {
+                    hazard_damage_per_turn: 30,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let next = advance(&board, &moves(vec![("CorneliusCodes", Direction::Up)]));
+        assert_eq!(next.snakes[0].health, 44);
+    }
+
+    #[test]
+    fn stacked_hazards_hurt_more() {
+        let me = Battlesnake {
+            head: Coord { x: 5, y: 5 },
+            body: vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }],
+            health: 75,
+            length: 2,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            hazards: vec![Coord { x: 5, y: 6 }, Coord { x: 5, y: 6 }],
+            snakes: vec![me],
+            ..Default::default()
+        };
+        let next = advance(&board, &moves(vec![("CorneliusCodes", Direction::Up)]));
+        assert_eq!(next.snakes[0].health, 46);
+    }
+
+    #[test]
+    fn starves_without_health() {
+        let me = Battlesnake {
+            head: Coord { x: 5, y: 5 },
+            body: vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }],
+            health: 1,
+            length: 2,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me],
+            ..Default::default()
+        };
+        let next = advance(&board, &moves(vec![("CorneliusCodes", Direction::Up)]));
+        assert!(next.snakes.is_empty());
+    }
+
+    #[test]
+    fn hits_the_wall() {
+        let me = Battlesnake {
+            head: Coord { x: 0, y: 5 },
+            body: vec![Coord { x: 0, y: 5 }, Coord { x: 1, y: 5 }],
+            length: 2,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me],
+            ..Default::default()
+        };
+        let next = advance(&board, &moves(vec![("CorneliusCodes", Direction::Left)]));
+        assert!(next.snakes.is_empty());
+    }
+
+    #[test]
+    fn wraps_around_the_board() {
+        let me = Battlesnake {
+            head: Coord { x: 0, y: 5 },
+            body: vec![Coord { x: 0, y: 5 }, Coord { x: 1, y: 5 }],
+            length: 2,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me],
+            ruleset: Ruleset {
+                name: RulesetName::Wrapped,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let next = advance(&board, &moves(vec![("CorneliusCodes", Direction::Left)]));
+        assert_eq!(next.snakes[0].head, Coord { x: 10, y: 5 });
+    }
+
+    #[test]
+    fn constrictors_always_grow() {
+        let me = Battlesnake {
+            head: Coord { x: 5, y: 5 },
+            body: vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }],
+            health: 50,
+            length: 2,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me],
+            ruleset: Ruleset {
+                name: RulesetName::Constrictor,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let next = advance(&board, &moves(vec![("CorneliusCodes", Direction::Up)]));
+        let me = &next.snakes[0];
+        assert_eq!(me.health, 100);
+        assert_eq!(me.length, 3);
+        assert_eq!(
+            me.body,
+            vec![
+                Coord { x: 5, y: 6 },
+                Coord { x: 5, y: 5 },
+                Coord { x: 5, y: 5 },
+            ]
+        );
+    }
+
+    #[test]
+    fn bites_themselves() {
+        let me = Battlesnake {
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 5, y: 4 },
+                Coord { x: 4, y: 4 },
+                Coord { x: 4, y: 5 },
+                Coord { x: 4, y: 6 },
+            ],
+            length: 5,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me],
+            ..Default::default()
+        };
+        let next = advance(&board, &moves(vec![("CorneliusCodes", Direction::Left)]));
+        assert!(next.snakes.is_empty());
+    }
+
+    #[test]
+    fn bites_hettie() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 5, y: 5 },
+            body: vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }],
+            length: 2,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 7, y: 6 },
+            body: vec![
+                Coord { x: 7, y: 6 },
+                Coord { x: 6, y: 6 },
+                Coord { x: 5, y: 6 },
+                Coord { x: 4, y: 6 },
+            ],
+            length: 4,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me, hettie],
+            ..Default::default()
+        };
+        let next = advance(
+            &board,
+            &moves(vec![("me", Direction::Up), ("hettie", Direction::Right)]),
+        );
+        assert_eq!(next.snakes.len(), 1);
+        assert_eq!(next.snakes[0].id, "hettie");
+    }
+
+    #[test]
+    fn follows_a_tail() {
+        let me = Battlesnake {
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 5, y: 4 },
+                Coord { x: 4, y: 4 },
+                Coord { x: 4, y: 5 },
+            ],
+            length: 4,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me],
+            ..Default::default()
+        };
+        let next = advance(&board, &moves(vec![("CorneliusCodes", Direction::Left)]));
+        assert_eq!(next.snakes.len(), 1);
+    }
+
+    #[test]
+    fn longer_snake_wins_head_to_head() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 5, y: 4 },
+                Coord { x: 5, y: 3 },
+            ],
+            length: 3,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 6, y: 6 },
+            body: vec![Coord { x: 6, y: 6 }, Coord { x: 7, y: 6 }],
+            length: 2,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me, hettie],
+            ..Default::default()
+        };
+        let next = advance(
+            &board,
+            &moves(vec![("me", Direction::Up), ("hettie", Direction::Left)]),
+        );
+        assert_eq!(next.snakes.len(), 1);
+        assert_eq!(next.snakes[0].id, "me");
+    }
+
+    #[test]
+    fn same_size_head_to_head_kills_both() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 5, y: 5 },
+            body: vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }],
+            length: 2,
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 6, y: 6 },
+            body: vec![Coord { x: 6, y: 6 }, Coord { x: 7, y: 6 }],
+            length: 2,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me, hettie],
+            ..Default::default()
+        };
+        let next = advance(
+            &board,
+            &moves(vec![("me", Direction::Up), ("hettie", Direction::Left)]),
+        );
+        assert!(next.snakes.is_empty());
+    }
+
+    #[test]
+    fn teammates_pass_through_each_other() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 5, y: 5 },
+            body: vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }],
+            length: 2,
+            squad: Some("corns".to_string()),
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 7, y: 6 },
+            body: vec![
+                Coord { x: 7, y: 6 },
+                Coord { x: 6, y: 6 },
+                Coord { x: 5, y: 6 },
+                Coord { x: 4, y: 6 },
+            ],
+            length: 4,
+            squad: Some("corns".to_string()),
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me, hettie],
+            ruleset: Ruleset {
+                name: RulesetName::Squad,
+                settings: RulesetSettings {
+                    squad: SquadSettings {
+                        allow_body_collisions: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let next = advance(
+            &board,
+            &moves(vec![("me", Direction::Up), ("hettie", Direction::Right)]),
+        );
+        assert_eq!(next.snakes.len(), 2);
+    }
+
+    #[test]
+    fn squads_fall_together() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 0, y: 5 },
+            body: vec![Coord { x: 0, y: 5 }, Coord { x: 1, y: 5 }],
+            length: 2,
+            squad: Some("corns".to_string()),
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 5, y: 5 },
+            body: vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }],
+            length: 2,
+            squad: Some("corns".to_string()),
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me, hettie],
+            ruleset: Ruleset {
+                name: RulesetName::Squad,
+                settings: RulesetSettings {
+                    squad: SquadSettings {
+                        shared_elimination: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let next = advance(
+            &board,
+            &moves(vec![("me", Direction::Left), ("hettie", Direction::Up)]),
+        );
+        assert!(next.snakes.is_empty());
+    }
+
+    #[test]
+    fn squads_share_health_and_length() {
+        let me = Battlesnake {
+            id: "me".to_string(),
+            head: Coord { x: 5, y: 5 },
+            body: vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }],
+            health: 30,
+            length: 2,
+            squad: Some("corns".to_string()),
+            ..Default::default()
+        };
+        let hettie = Battlesnake {
+            id: "hettie".to_string(),
+            name: "Hettie".to_string(),
+            head: Coord { x: 8, y: 5 },
+            body: vec![
+                Coord { x: 8, y: 5 },
+                Coord { x: 8, y: 4 },
+                Coord { x: 8, y: 3 },
+            ],
+            health: 80,
+            length: 3,
+            squad: Some("corns".to_string()),
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![me, hettie],
+            ruleset: Ruleset {
+                name: RulesetName::Squad,
+                settings: RulesetSettings {
+                    squad: SquadSettings {
+                        shared_health: true,
+                        shared_length: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let next = advance(
+            &board,
+            &moves(vec![("me", Direction::Up), ("hettie", Direction::Up)]),
+        );
+        let me = next.snakes.iter().find(|snake| snake.id == "me").unwrap();
+        assert_eq!(me.health, 79);
+        assert_eq!(me.length, 3);
+        assert_eq!(me.body.len(), 3);
+    }
+}
@@ -0,0 +1,410 @@
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::bitboard::Bitboard;
+use crate::grid::Grid;
+use crate::logic::{self, MOVES};
+use crate::rules;
+use crate::{Battlesnake, Board, Direction, Game, GameSource};
+
+// Leaves time for the response to travel back to the game engine
+const SAFETY_MARGIN: Duration = Duration::from_millis(200);
+// League games count, so Cornelius doesn't cut it as fine
+const LEAGUE_SAFETY_MARGIN: Duration = Duration::from_millis(300);
+const MAX_DEPTH: u32 = 16;
+pub const LOSS: i32 = -1_000_000;
+pub const WIN: i32 = 1_000_000;
+
+pub fn deadline(game: &Game, started: Instant) -> Instant {
+    let margin = match game.source {
+        GameSource::League => LEAGUE_SAFETY_MARGIN,
+        _ => SAFETY_MARGIN,
+    };
+    started + Duration::from_millis(game.timeout.into()).saturating_sub(margin)
+}
+
+// How a search scores the boards it reaches, so the same alpha-beta can play
+// a crowded board or a duel
+pub trait Evaluator {
+    // How many moves ahead it's worth looking
+    fn max_depth(&self) -> u32;
+
+    // The value once the game is over for Cornelius, going by the ids of the snakes still in it,
+    // or None if it goes on. Gets the depth still left, so that sooner wins and later losses
+    // are worth more.
+    fn game_over(&self, survivors: &[&str], my_id: &str, depth: u32) -> Option<i32>;
+
+    // The value of a board at the end of the search, with the game still going
+    fn value(&self, board: &Board, me: &Battlesnake) -> i32;
+}
+
+// Paranoid alpha-beta: Cornelius picks the move that is best for them
+// assuming every other snake moves together to make it as bad as possible.
+pub struct Paranoid;
+
+impl Evaluator for Paranoid {
+    fn max_depth(&self) -> u32 {
+        MAX_DEPTH
+    }
+
+    fn game_over(&self, survivors: &[&str], my_id: &str, depth: u32) -> Option<i32> {
+        if survivors.contains(&my_id) {
+            None
+        } else {
+            Some(LOSS - depth as i32)
+        }
+    }
+
+    fn value(&self, board: &Board, me: &Battlesnake) -> i32 {
+        logic::value_of_board(board, me)
+    }
+}
+
+// Looks further ahead each pass until the deadline, and returns the move from the deepest finished pass.
+// Falls back to the greedy choice if not even one pass finishes in time.
+pub fn best_move<E: Evaluator>(
+    evaluator: &E,
+    board: &Board,
+    me: &Battlesnake,
+    deadline: Instant,
+) -> Direction {
+    let mut chosen = logic::greedy_move(board, me);
+    for depth in 1..=evaluator.max_depth() {
+        match search(evaluator, board, me, depth, Some(deadline)) {
+            Some(best) => chosen = best,
+            None => break,
+        }
+    }
+    chosen
+}
+
+// Tries the moves that look best first, so the rest get pruned sooner
+pub fn search<E: Evaluator>(
+    evaluator: &E,
+    board: &Board,
+    me: &Battlesnake,
+    depth: u32,
+    deadline: Option<Instant>,
+) -> Option<Direction> {
+    let pass = Pass {
+        evaluator,
+        original: board,
+        my_id: &me.id,
+        deadline,
+    };
+    let position = Position::new(board);
+    let mut ordered = logic::score_moves(board, me);
+    ordered.sort_by_key(|(_, value)| Reverse(*value));
+
+    let mut alpha = pass.floor();
+    let mut chosen = ordered[0].0;
+    for (candidate, _) in ordered {
+        let value = pass.min_value(&position, candidate, depth, alpha, WIN)?;
+        if value > alpha {
+            alpha = value;
+            chosen = candidate;
+        }
+    }
+    Some(chosen)
+}
+
+// Everything that stays the same through one pass of the search
+struct Pass<'a, E> {
+    evaluator: &'a E,
+    // The board the search started from, for everything a bitboard doesn't keep
+    original: &'a Board,
+    my_id: &'a str,
+    deadline: Option<Instant>,
+}
+
+impl<E: Evaluator> Pass<'_, E> {
+    // Worse than any loss, so that even losing moves get picked when there's nothing else
+    fn floor(&self) -> i32 {
+        LOSS - self.evaluator.max_depth() as i32 - 1
+    }
+
+    fn max_value(&self, position: &Position, depth: u32, mut alpha: i32, beta: i32) -> Option<i32> {
+        let survivors = position.survivors(self.original);
+        if let Some(value) = self.evaluator.game_over(&survivors, self.my_id, depth) {
+            return Some(value);
+        }
+        if depth == 0 {
+            let board = position.board(self.original);
+            let me = board.snakes.iter().find(|snake| snake.id == self.my_id)?;
+            return Some(self.evaluator.value(&board, me));
+        }
+
+        let mut best = self.floor();
+        for candidate in MOVES.iter() {
+            let value = self.min_value(position, *candidate, depth, alpha, beta)?;
+            best = best.max(value);
+            alpha = alpha.max(value);
+            if alpha >= beta {
+                break;
+            }
+        }
+        Some(best)
+    }
+
+    fn min_value(
+        &self,
+        position: &Position,
+        chosen: Direction,
+        depth: u32,
+        alpha: i32,
+        mut beta: i32,
+    ) -> Option<i32> {
+        let mut worst = WIN + self.evaluator.max_depth() as i32 + 1;
+        for mut moves in opponent_moves(position.options(self.original, self.my_id)) {
+            if let Some(deadline) = self.deadline {
+                if Instant::now() >= deadline {
+                    return None;
+                }
+            }
+            moves.insert(self.my_id.to_string(), chosen);
+            let next = position.advance(self.original, &moves);
+            let value = self.max_value(&next, depth - 1, alpha, beta)?;
+            worst = worst.min(value);
+            beta = beta.min(value);
+            if alpha >= beta {
+                break;
+            }
+        }
+        Some(worst)
+    }
+}
+
+// Where the search has got to. Stepping a bitboard saves copying the whole board at every
+// node, so the full Board is only rebuilt to score the leaves. Squads and stacked hazards
+// don't fit on a bitboard, so those games are searched on the Board itself.
+enum Position {
+    Bits(Bitboard),
+    Full(Board),
+}
+
+impl Position {
+    fn new(board: &Board) -> Position {
+        match Bitboard::from_board(board) {
+            Some(bitboard) => Position::Bits(bitboard),
+            None => Position::Full(board.clone()),
+        }
+    }
+
+    fn board<'a>(&'a self, original: &Board) -> Cow<'a, Board> {
+        match self {
+            Position::Bits(bitboard) => Cow::Owned(bitboard.to_board(original)),
+            Position::Full(board) => Cow::Borrowed(board),
+        }
+    }
+
+    fn survivors<'a>(&'a self, original: &'a Board) -> Vec<&'a str> {
+        match self {
+            Position::Bits(bitboard) => bitboard
+                .survivors()
+                .map(|index| original.snakes[index].id.as_str())
+                .collect(),
+            Position::Full(board) => board.snakes.iter().map(|snake| snake.id.as_str()).collect(),
+        }
+    }
+
+    // The moves each of the other snakes could make, skipping moves that would kill them
+    // outright unless they have nothing better. Teammates aren't out to get Cornelius,
+    // so they only ever make the move that looks best to them.
+    fn options(&self, original: &Board, my_id: &str) -> Vec<(String, Vec<Direction>)> {
+        let teammate = |snake: &Battlesnake| {
+            original
+                .snakes
+                .iter()
+                .any(|other| other.id == my_id && snake.is_teammate(other))
+        };
+        let mut options = Vec::new();
+        match self {
+            Position::Bits(bitboard) => {
+                for (slot, index) in bitboard.survivors().enumerate() {
+                    let snake = &original.snakes[index];
+                    if snake.id == my_id {
+                        continue;
+                    }
+                    let moves = if teammate(snake) {
+                        let board = bitboard.to_board(original);
+                        let snake = board.snakes.iter().find(|other| other.id == snake.id);
+                        snake
+                            .map(|snake| logic::greedy_move(&board, snake))
+                            .into_iter()
+                            .collect()
+                    } else {
+                        bitboard.legal_moves(slot)
+                    };
+                    options.push((snake.id.clone(), moves));
+                }
+            }
+            Position::Full(board) => {
+                let grid = Grid::new(board);
+                for snake in board.snakes.iter().filter(|snake| snake.id != my_id) {
+                    let moves = if teammate(snake) {
+                        vec![logic::greedy_move(board, snake)]
+                    } else {
+                        MOVES
+                            .iter()
+                            .copied()
+                            .filter(|candidate| {
+                                logic::valid_move(&board.step(&snake.head, *candidate), &grid)
+                            })
+                            .collect()
+                    };
+                    options.push((snake.id.clone(), moves));
+                }
+            }
+        }
+        options
+    }
+
+    fn advance(&self, original: &Board, moves: &HashMap<String, Direction>) -> Position {
+        match self {
+            Position::Bits(bitboard) => {
+                let moves: Vec<Direction> = bitboard
+                    .survivors()
+                    .map(|index| moves.get(&original.snakes[index].id).copied())
+                    .map(|chosen| chosen.unwrap_or(Direction::Up))
+                    .collect();
+                let mut next = bitboard.clone();
+                next.advance(&moves);
+                Position::Bits(next)
+            }
+            Position::Full(board) => Position::Full(rules::advance(board, moves)),
+        }
+    }
+}
+
+// Every combination of the moves the other snakes could make. Snakes with nowhere
+// to go still have to move somewhere.
+fn opponent_moves(options: Vec<(String, Vec<Direction>)>) -> Vec<HashMap<String, Direction>> {
+    let mut combinations = vec![HashMap::new()];
+    for (id, mut moves) in options {
+        if moves.is_empty() {
+            moves.push(Direction::Up);
+        }
+
+        let (id, moves) = (&id, &moves);
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                moves.iter().map(move |candidate| {
+                    let mut combination = combination.clone();
+                    combination.insert(id.clone(), *candidate);
+                    combination
+                })
+            })
+            .collect();
+    }
+    combinations
+}
+
+#[cfg(test)]
+mod deadline_tests {
+    use super::*;
+
+    #[test]
+    fn league_games_leave_more_time() {
+        let started = Instant::now();
+        let arena = Game {
+            source: GameSource::Arena,
+            timeout: 500,
+            ..Default::default()
+        };
+        let league = Game {
+            source: GameSource::League,
+            timeout: 500,
+            ..Default::default()
+        };
+        assert_eq!(
+            deadline(&arena, started),
+            started + Duration::from_millis(300)
+        );
+        assert_eq!(
+            deadline(&league, started),
+            started + Duration::from_millis(200)
+        );
+    }
+}
+
+#[cfg(test)]
+mod opponent_moves_tests {
+    use super::*;
+    use crate::{Coord, Ruleset, RulesetName};
+
+    fn snake(id: &str, x: i32, squad: &str) -> Battlesnake {
+        Battlesnake {
+            id: id.to_string(),
+            head: Coord { x, y: 5 },
+            body: vec![Coord { x, y: 5 }, Coord { x, y: 4 }, Coord { x, y: 3 }],
+            length: 3,
+            squad: Some(squad.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn teammates_are_not_out_to_get_cornelius() {
+        let me = snake("me", 2, "corneliuses");
+        let board = Board {
+            height: 11,
+            width: 11,
+            snakes: vec![
+                me.clone(),
+                snake("cornelia", 4, "corneliuses"),
+                snake("hettie", 8, "hetties"),
+            ],
+            ruleset: Ruleset {
+                name: RulesetName::Squad,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let combinations = opponent_moves(Position::new(&board).options(&board, &me.id));
+        // Hettie can go any way but back into her own neck, Cornelia only goes one way
+        assert_eq!(combinations.len(), 3);
+        let cornelia = combinations[0]["cornelia"];
+        assert!(combinations
+            .iter()
+            .all(|moves| moves["cornelia"] == cornelia));
+    }
+}
+
+#[cfg(test)]
+mod search_tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn skips_food_at_the_end_of_a_dead_end() {
+        let (board, me) = fixtures::dead_end();
+        assert_eq!(logic::greedy_move(&board, &me), Direction::Left);
+        assert_ne!(
+            search(&Paranoid, &board, &me, 3, None),
+            Some(Direction::Left)
+        );
+    }
+
+    #[test]
+    fn searches_the_full_board_when_the_bitboard_cannot_play_it() {
+        let (mut board, me) = fixtures::dead_end();
+        board.ruleset.settings.squad.shared_health = true;
+        assert!(matches!(Position::new(&board), Position::Full(_)));
+        assert_ne!(
+            search(&Paranoid, &board, &me, 2, None),
+            Some(Direction::Left)
+        );
+    }
+
+    #[test]
+    fn falls_back_to_greedy_without_time() {
+        let (board, me) = fixtures::facing_the_wall();
+        assert_eq!(
+            best_move(&Paranoid, &board, &me, Instant::now()),
+            Direction::Down
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::grid::Grid;
+use crate::logic::{self, Arrival, HUNGER_MARGIN};
+use crate::{Battlesnake, Board, Coord, Direction, RulesetName};
+
+// Solo games reward the turns survived, not kills or length
+pub fn is_solo(board: &Board, me: &Battlesnake) -> bool {
+    board.ruleset.name == RulesetName::Solo || board.snakes.iter().all(|snake| snake.id == me.id)
+}
+
+pub fn best_move(board: &Board, me: &Battlesnake) -> Direction {
+    let grid = Grid::for_snake(board, me);
+    let options = logic::move_options(board, &grid, me);
+
+    // Hungry once the closest food any move gets them to would leave little to spare
+    let hungry = options
+        .iter()
+        .filter_map(|(_, _, arrivals)| nearest_food(board, arrivals))
+        .map(|meal| meal.health)
+        .max()
+        .map_or(false, |leftover| leftover <= HUNGER_MARGIN);
+
+    let mut best = None;
+    for (candidate, spot, arrivals) in options.iter() {
+        if let Some(value) = value_of_solo_move(spot, arrivals, hungry, board, &grid, me) {
+            if best.map_or(true, |(_, best_value)| value > best_value) {
+                best = Some((*candidate, value));
+            }
+        }
+    }
+
+    match best {
+        Some((chosen, _)) => chosen,
+        None => logic::greedy_move(board, me),
+    }
+}
+
+// Staying able to chase their own tail keeps Cornelius alive forever,
+// so that matters most, then room to move, then food only when they need it.
+// Moves that would finish Cornelius off, hazards included, are worth nothing at all.
+fn value_of_solo_move(
+    spot: &Coord,
+    arrivals: &HashMap<Coord, Arrival>,
+    hungry: bool,
+    board: &Board,
+    grid: &Grid,
+    me: &Battlesnake,
+) -> Option<i32> {
+    if arrivals.is_empty() {
+        return None;
+    }
+
+    // Every cell Cornelius could get to is the remaining space
+    let mut value = arrivals.len() as i32;
+
+    // Eating keeps the tail where it is, otherwise the segment before it becomes the tail
+    let ate = grid.has_food(spot);
+    let tail = if ate {
+        me.body.last()
+    } else {
+        me.body.iter().rev().nth(1)
+    };
+    if let Some(arrival) = tail.and_then(|tail| arrivals.get(tail)) {
+        value += 1000 - i32::from(arrival.turn);
+    }
+
+    if hungry {
+        let meal = nearest_food(board, arrivals);
+        value -= 10 * meal.map_or(board.width + board.height, |meal| i32::from(meal.turn));
+        if ate {
+            value += 500;
+        }
+    } else if ate {
+        value -= 500;
+    }
+
+    Some(value)
+}
+
+// The first food Cornelius can get to, with no rivals around to race
+fn nearest_food(board: &Board, arrivals: &HashMap<Coord, Arrival>) -> Option<Arrival> {
+    board
+        .food
+        .iter()
+        .filter_map(|food| arrivals.get(food))
+        .min_by_key(|meal| (meal.turn, Reverse(meal.health)))
+        .copied()
+}
+
+#[cfg(test)]
+mod best_move_tests {
+    use super::*;
+
+    #[test]
+    fn chases_their_tail_instead_of_eating() {
+        let me = Battlesnake {
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 5, y: 4 },
+                Coord { x: 6, y: 4 },
+                Coord { x: 6, y: 5 },
+            ],
+            health: 90,
+            length: 4,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 4, y: 5 }],
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        assert_eq!(best_move(&board, &me), Direction::Right);
+    }
+
+    #[test]
+    fn eats_when_hungry() {
+        let me = Battlesnake {
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 5, y: 4 },
+                Coord { x: 6, y: 4 },
+                Coord { x: 6, y: 5 },
+            ],
+            health: 4,
+            length: 4,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 3, y: 5 }],
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        assert_eq!(best_move(&board, &me), Direction::Left);
+    }
+
+    #[test]
+    fn goes_round_hazards_to_eat() {
+        let me = Battlesnake {
+            head: Coord { x: 5, y: 5 },
+            body: vec![
+                Coord { x: 5, y: 5 },
+                Coord { x: 5, y: 4 },
+                Coord { x: 5, y: 3 },
+            ],
+            health: 8,
+            length: 3,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 2, y: 5 }, Coord { x: 5, y: 9 }],
+            hazards: vec![
+                Coord { x: 3, y: 5 },
+                Coord { x: 3, y: 6 },
+                Coord { x: 3, y: 4 },
+            ],
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        // The food on the left looks closer, but the hazards in the way would be the end of them
+        assert_eq!(best_move(&board, &me), Direction::Up);
+    }
+
+    #[test]
+    fn only_me_left_is_solo() {
+        let me = Battlesnake::default();
+        let board = Board {
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        assert!(is_solo(&board, &me));
+    }
+}
@@ -0,0 +1,121 @@
+use log::info;
+use rocket_contrib::json::JsonValue;
+
+use crate::personality::Appearance;
+use crate::{logic, mcts};
+use crate::{Battlesnake, Board, Game, MoveResponse};
+
+pub const DEFAULT_STRATEGY: &str = "search";
+pub const STRATEGIES: [&str; 3] = ["greedy", "search", "mcts"];
+
+// Everything the server needs from a brain. Only choosing a move is required,
+// the rest falls back to what Cornelius has always done.
+pub trait Strategy: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn info(&self, appearance: &Appearance) -> JsonValue {
+        logic::get_info(appearance)
+    }
+
+    fn start(&self, game: &Game, turn: &u32, board: &Board, me: &Battlesnake) {
+        logic::start(game, turn, board, me)
+    }
+
+    fn get_move(&self, game: &Game, turn: &u32, board: &Board, me: &Battlesnake) -> MoveResponse;
+
+    fn end(&self, game: &Game, turn: &u32, board: &Board, me: &Battlesnake) {
+        logic::end(game, turn, board, me)
+    }
+}
+
+// Picks the best looking move without looking ahead
+pub struct Greedy;
+
+impl Strategy for Greedy {
+    fn name(&self) -> &'static str {
+        "greedy"
+    }
+
+    fn get_move(&self, game: &Game, _turn: &u32, board: &Board, me: &Battlesnake) -> MoveResponse {
+        let chosen = logic::greedy_move(board, me);
+
+        info!("{} MOVE {} (greedy)", game.id, chosen);
+
+        MoveResponse::new(chosen, logic::shout(board, me, chosen))
+    }
+}
+
+// Looks ahead with alpha-beta search, or plays for survival when alone
+pub struct Search;
+
+impl Strategy for Search {
+    fn name(&self) -> &'static str {
+        "search"
+    }
+
+    fn get_move(&self, game: &Game, turn: &u32, board: &Board, me: &Battlesnake) -> MoveResponse {
+        let chosen = logic::get_move(game, turn, board, me);
+        MoveResponse::new(chosen, logic::shout(board, me, chosen))
+    }
+}
+
+pub struct Mcts;
+
+impl Strategy for Mcts {
+    fn name(&self) -> &'static str {
+        "mcts"
+    }
+
+    fn get_move(&self, game: &Game, turn: &u32, board: &Board, me: &Battlesnake) -> MoveResponse {
+        let chosen = mcts::get_move(game, turn, board, me);
+        MoveResponse::new(chosen, logic::shout(board, me, chosen))
+    }
+}
+
+// Add new strategies here to make them selectable by name
+pub fn from_name(name: &str) -> Option<Box<dyn Strategy>> {
+    match name {
+        "greedy" => Some(Box::new(Greedy)),
+        "search" => Some(Box::new(Search)),
+        "mcts" => Some(Box::new(Mcts)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod from_name_tests {
+    use super::*;
+    use crate::{Coord, Direction};
+
+    #[test]
+    fn every_listed_strategy_is_registered() {
+        for name in STRATEGIES.iter() {
+            let strategy = from_name(name).unwrap();
+            assert_eq!(strategy.name(), *name);
+        }
+        assert!(STRATEGIES.contains(&DEFAULT_STRATEGY));
+    }
+
+    #[test]
+    fn unknown_strategy() {
+        assert!(from_name("telepathy").is_none());
+    }
+
+    #[test]
+    fn greedy_avoids_the_wall() {
+        let head = Coord { x: 9, y: 9 };
+        let me = Battlesnake {
+            body: vec![head, head.left(), head.left().left()],
+            head,
+            ..Default::default()
+        };
+        let board = Board {
+            height: 10,
+            width: 10,
+            snakes: vec![me.clone()],
+            ..Default::default()
+        };
+        let chosen = Greedy.get_move(&Game::default(), &0, &board, &me);
+        assert_eq!(chosen.chosen(), Direction::Down);
+    }
+}